@@ -0,0 +1,131 @@
+use crate::chess_engine::pieces::Color;
+
+use super::Bitboard;
+
+// (rank, file) steps, the same convention MoveOffset uses
+const KNIGHT_STEPS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_STEPS: [(i8, i8); 8] = [
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+// the first four rays walk towards higher squares, the last four towards lower ones
+const RAY_STEPS: [(i8, i8); 8] = [
+    (1, 0),
+    (0, 1),
+    (1, 1),
+    (1, -1),
+    (-1, 0),
+    (0, -1),
+    (-1, -1),
+    (-1, 1),
+];
+const NORTH: usize = 0;
+const EAST: usize = 1;
+const NORTH_EAST: usize = 2;
+const NORTH_WEST: usize = 3;
+const SOUTH: usize = 4;
+const WEST: usize = 5;
+const SOUTH_WEST: usize = 6;
+const SOUTH_EAST: usize = 7;
+
+static KNIGHT_ATTACKS: [Bitboard; 64] = step_table(&KNIGHT_STEPS);
+static KING_ATTACKS: [Bitboard; 64] = step_table(&KING_STEPS);
+static PAWN_ATTACKS: [[Bitboard; 64]; 2] = [step_table(&[(-1, -1), (-1, 1)]), step_table(&[(1, -1), (1, 1)])];
+static RAYS: [[Bitboard; 64]; 8] = ray_tables();
+
+const fn step_table(steps: &[(i8, i8)]) -> [Bitboard; 64] {
+    let mut table = [Bitboard(0); 64];
+    let mut square = 0;
+    while square < 64 {
+        let rank = (square / 8) as i8;
+        let file = (square % 8) as i8;
+        let mut bits = 0u64;
+        let mut i = 0;
+        while i < steps.len() {
+            let (r, f) = (rank + steps[i].0, file + steps[i].1);
+            if r >= 0 && r < 8 && f >= 0 && f < 8 {
+                bits |= 1 << (r * 8 + f);
+            }
+            i += 1;
+        }
+        table[square] = Bitboard(bits);
+        square += 1;
+    }
+    table
+}
+const fn ray_tables() -> [[Bitboard; 64]; 8] {
+    let mut tables = [[Bitboard(0); 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (dr, df) = RAY_STEPS[direction];
+        let mut square = 0;
+        while square < 64 {
+            let mut r = (square / 8) as i8 + dr;
+            let mut f = (square % 8) as i8 + df;
+            let mut bits = 0u64;
+            while r >= 0 && r < 8 && f >= 0 && f < 8 {
+                bits |= 1 << (r * 8 + f);
+                r += dr;
+                f += df;
+            }
+            tables[direction][square] = Bitboard(bits);
+            square += 1;
+        }
+        direction += 1;
+    }
+    tables
+}
+
+pub fn knight_attacks(square: u8) -> Bitboard {
+    KNIGHT_ATTACKS[square as usize]
+}
+pub fn king_attacks(square: u8) -> Bitboard {
+    KING_ATTACKS[square as usize]
+}
+// the squares a pawn of `color` standing on `square` captures on
+pub fn pawn_attacks(color: Color, square: u8) -> Bitboard {
+    PAWN_ATTACKS[color.index()][square as usize]
+}
+// everything along the ray up to and including the first piece in the way
+fn ray_attacks(direction: usize, square: u8, occupied: Bitboard) -> Bitboard {
+    let ray = RAYS[direction][square as usize];
+    let blockers = (ray & occupied).0;
+    if blockers == 0 {
+        return ray;
+    }
+    let blocker = match direction < SOUTH {
+        true => blockers.trailing_zeros(),
+        false => 63 - blockers.leading_zeros(),
+    };
+    ray ^ RAYS[direction][blocker as usize]
+}
+pub fn rook_attacks(square: u8, occupied: Bitboard) -> Bitboard {
+    ray_attacks(NORTH, square, occupied)
+        | ray_attacks(EAST, square, occupied)
+        | ray_attacks(SOUTH, square, occupied)
+        | ray_attacks(WEST, square, occupied)
+}
+pub fn bishop_attacks(square: u8, occupied: Bitboard) -> Bitboard {
+    ray_attacks(NORTH_EAST, square, occupied)
+        | ray_attacks(NORTH_WEST, square, occupied)
+        | ray_attacks(SOUTH_EAST, square, occupied)
+        | ray_attacks(SOUTH_WEST, square, occupied)
+}
+pub fn queen_attacks(square: u8, occupied: Bitboard) -> Bitboard {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
@@ -0,0 +1,95 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::{BoardPosition, File, Rank};
+
+// one bit per square, a1 is bit 0, b1 bit 1 and h8 bit 63
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+
+    pub const fn from_square(square: u8) -> Bitboard {
+        Bitboard(1 << square)
+    }
+    pub fn contains(&self, square: u8) -> bool {
+        self.0 & (1 << square) != 0
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+    // the lowest set square, if there is one
+    pub fn first(&self) -> Option<u8> {
+        match self.0 {
+            0 => None,
+            bits => Some(bits.trailing_zeros() as u8),
+        }
+    }
+    pub fn set(&mut self, square: u8) {
+        self.0 |= 1 << square;
+    }
+    pub fn clear(&mut self, square: u8) {
+        self.0 &= !(1 << square);
+    }
+}
+impl BoardPosition {
+    pub fn square(&self) -> u8 {
+        ((i8::from(self.rank) - 1) * 8 + i8::from(self.file) - 1) as u8
+    }
+    pub fn from_square(square: u8) -> BoardPosition {
+        BoardPosition::new(
+            File::ALL[(square % 8) as usize],
+            Rank::ALL[(square / 8) as usize],
+        )
+    }
+}
+impl Iterator for Bitboard {
+    type Item = u8;
+    fn next(&mut self) -> Option<Self::Item> {
+        let square = self.first()?;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+}
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0)
+    }
+}
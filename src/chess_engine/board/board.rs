@@ -1,21 +1,40 @@
+use super::attacks::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks};
+use super::Bitboard;
 use super::BoardPosition;
+use super::CastlingRights;
 use super::File;
 use super::Rank;
+use super::zobrist::{castling_key, piece_key, side_key};
 use crate::chess_engine::history::History;
 use crate::chess_engine::pieces::Action;
 use crate::chess_engine::pieces::Color;
+use crate::chess_engine::pieces::InnerPiece;
+use crate::chess_engine::pieces::MovementOptions;
 use crate::chess_engine::pieces::Piece;
+use crate::chess_engine::render::TextRenderer;
 use std::fmt::Display;
 use std::ops::Index;
-use std::ops::IndexMut;
 
 #[derive(Debug, Clone)]
 pub struct BoardRank([Option<Piece>; 8]);
 #[derive(Debug, Clone)]
 pub struct Board {
+    // the mailbox answers "what is on this square", the bitboards "where are the pieces of this kind"
     inner_board: [BoardRank; 8],
-    turn: Color,
+    bitboards: [[Bitboard; 6]; 2],
+    occupancy: [Bitboard; 2],
+    pub(super) turn: Color,
     history:History,
+    pub(super) castling_rights: CastlingRights,
+    pub(super) en_passant: Option<BoardPosition>,
+    pub(super) halfmove_clock: u32,
+    pub(super) fullmove_number: u32,
+    // zobrist key of the position, kept up to date by every change below
+    pub(super) hash: u64,
+    // the en passant part of the hash, it depends on the pawns around when the square was set
+    en_passant_hash: u64,
+    // the keys of every position reached so far, the current one last, for spotting repetitions
+    positions: Vec<u64>,
 }
 
 impl Board {
@@ -26,8 +45,7 @@ impl Board {
         self[&pos.rank][&pos.file].is_some_and(|p|p.color==color)
     }
     pub fn new() -> Board {
-        Board {
-            inner_board: [
+        let layout = [
                 [
                     Some(Piece::new_rook(Color::Black)),
                     Some(Piece::new_knight(Color::Black)),
@@ -54,38 +72,226 @@ impl Board {
                     Some(Piece::new_knight(Color::White)),
                     Some(Piece::new_rook(Color::White)),
                 ],
-            ]
-            .map(|v| BoardRank(v)),
+            ];
+        let mut board = Board::empty();
+        board.castling_rights = CastlingRights::all();
+        for (rank, row) in Rank::ALL.iter().rev().zip(layout) {
+            for (file, piece) in File::ALL.iter().zip(row) {
+                if let Some(piece) = piece {
+                    board.put_piece(BoardPosition::new(*file, *rank), piece);
+                }
+            }
+        }
+        board.reset_hash();
+        board
+    }
+    pub(super) fn empty() -> Board {
+        Board {
+            inner_board: [(); 8].map(|_| BoardRank([None; 8])),
+            bitboards: [[Bitboard::EMPTY; 6]; 2],
+            occupancy: [Bitboard::EMPTY; 2],
             turn: Color::White,
-            history:History(Vec::new())
+            history: History::default(),
+            castling_rights: CastlingRights::none(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            hash: 0,
+            en_passant_hash: 0,
+            positions: Vec::new(),
+        }
+    }
+    // starts the hash and the position list over from whatever is on the board now
+    pub(super) fn reset_hash(&mut self) {
+        self.hash = self.compute_hash();
+        self.en_passant_hash = self.en_passant_key(self.en_passant);
+        self.positions = vec![self.hash];
+    }
+    // all changes to the squares go through these two so the bitboards stay in sync
+    pub(crate) fn put_piece(&mut self, pos: BoardPosition, mut piece: Piece) {
+        piece.pos = Some(pos);
+        let square = pos.square();
+        self.bitboards[piece.color.index()][piece.type_of_piece().index()].set(square);
+        self.occupancy[piece.color.index()].set(square);
+        self.hash ^= piece_key(piece.color, piece.type_of_piece(), square);
+        self.inner_board[rank_index(&pos.rank)].0[file_index(&pos.file)] = Some(piece);
+    }
+    pub(crate) fn remove_piece(&mut self, pos: &BoardPosition) -> Option<Piece> {
+        let piece = self.inner_board[rank_index(&pos.rank)].0[file_index(&pos.file)].take()?;
+        let square = pos.square();
+        self.bitboards[piece.color.index()][piece.type_of_piece().index()].clear(square);
+        self.occupancy[piece.color.index()].clear(square);
+        self.hash ^= piece_key(piece.color, piece.type_of_piece(), square);
+        Some(piece)
+    }
+    pub fn pieces_of(&self, color: Color, piece: InnerPiece) -> Bitboard {
+        self.bitboards[color.index()][piece.index()]
+    }
+    pub fn occupancy(&self, color: Color) -> Bitboard {
+        self.occupancy[color.index()]
+    }
+    pub fn occupied(&self) -> Bitboard {
+        self.occupancy[0] | self.occupancy[1]
+    }
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+    pub fn en_passant(&self) -> Option<BoardPosition> {
+        self.en_passant
+    }
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+    // how often the current position has been on the board, counting this time
+    pub fn repetitions(&self) -> usize {
+        // a capture or pawn move can't be taken back, so nothing before it can come again
+        self.positions
+            .iter()
+            .rev()
+            .take(self.halfmove_clock as usize + 1)
+            .step_by(2)
+            .filter(|hash| **hash == self.hash)
+            .count()
+    }
+    pub(crate) fn set_en_passant(&mut self, en_passant: Option<BoardPosition>) {
+        self.hash ^= self.en_passant_hash;
+        self.en_passant = en_passant;
+        self.en_passant_hash = self.en_passant_key(en_passant);
+        self.hash ^= self.en_passant_hash;
+    }
+    pub(crate) fn revoke_castling_rights(&mut self, from: &BoardPosition, to: &BoardPosition) {
+        self.hash ^= castling_key(&self.castling_rights);
+        self.castling_rights.remove_for_square(from);
+        self.castling_rights.remove_for_square(to);
+        self.hash ^= castling_key(&self.castling_rights);
+    }
+    // called once a move has been played, pawn moves and captures reset the fifty move count
+    pub(crate) fn finish_turn(&mut self, reset_halfmove_clock: bool) {
+        match reset_halfmove_clock {
+            true => self.halfmove_clock = 0,
+            false => self.halfmove_clock += 1,
+        }
+        if self.turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.opposite();
+        self.hash ^= side_key();
+        self.positions.push(self.hash);
+    }
+    pub(crate) fn rewind_turn(
+        &mut self,
+        castling_rights: CastlingRights,
+        en_passant: Option<BoardPosition>,
+        halfmove_clock: u32,
+    ) {
+        self.positions.pop();
+        self.turn = self.turn.opposite();
+        self.hash ^= side_key();
+        if self.turn == Color::Black {
+            self.fullmove_number -= 1;
         }
+        self.hash ^= castling_key(&self.castling_rights) ^ castling_key(&castling_rights);
+        self.castling_rights = castling_rights;
+        self.set_en_passant(en_passant);
+        self.halfmove_clock = halfmove_clock;
     }
-    pub fn move_piece(&mut self,action: Action){
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+    pub fn move_piece(&mut self,mut action: Action){
         action.execute(self);
         self.history.add(action);
     }
-    pub fn get_movement_options() {}
-    pub fn into_iter(&self)->Box<dyn Iterator<Item = Piece>+ '_> {
-        Box::new(self.inner_board.iter().map(|a|a.0.into_iter().flatten().collect::<Vec<Piece>>()).flatten())
+    pub fn undo(&mut self) -> Option<Action> {
+        let action = self.history.step_back()?.clone();
+        action.undo(self);
+        Some(action)
     }
-}
-impl IndexMut<&Rank> for Board {
-    fn index_mut(&mut self, index: &Rank) -> &mut Self::Output {
-        self.inner_board
-            .get_mut(<&Rank as Into<i8>>::into(index) as usize)
-            .expect("Rank is bigger than board")
+    pub fn redo(&mut self) -> Option<Action> {
+        let mut action = self.history.step_forward()?.clone();
+        action.execute(self);
+        Some(action)
+    }
+    // takes back every move after the first `ply` for good, there is nothing left to redo
+    pub fn truncate_to(&mut self, ply: usize) {
+        while self.history.len() > ply {
+            self.undo();
+        }
+        self.history.truncate_to(ply);
+    }
+    // every move the side to move could make, ignoring whether it leaves its own king in check
+    pub fn get_movement_options(&self) -> MovementOptions {
+        self.occupancy(self.turn)
+            .filter_map(|square| self[&BoardPosition::from_square(square)])
+            .filter_map(|piece| piece.get_movement_options(self))
+            .flatten()
+            .collect()
+    }
+    pub fn legal_moves(&self) -> MovementOptions {
+        let mut board = self.clone();
+        self.get_movement_options()
+            .into_iter()
+            .filter(|action| {
+                let mut action = action.clone();
+                action.execute(&mut board);
+                let legal = !board.is_in_check(self.turn);
+                action.undo(&mut board);
+                legal
+            })
+            .collect()
+    }
+    pub fn is_in_check(&self, color: Color) -> bool {
+        self.pieces_of(color, InnerPiece::King)
+            .first()
+            .is_some_and(|square| self.is_square_attacked(square, color.opposite()))
+    }
+    pub fn is_attacked(&self, pos: &BoardPosition, by: Color) -> bool {
+        self.is_square_attacked(pos.square(), by)
+    }
+    // looks from the square outwards with every piece kind and checks if it sees one of its own
+    pub fn is_square_attacked(&self, square: u8, by: Color) -> bool {
+        let occupied = self.occupied();
+        let queens = self.pieces_of(by, InnerPiece::Queen);
+        // a pawn of `by` attacks us exactly where one of our pawns here would attack it
+        !(pawn_attacks(by.opposite(), square) & self.pieces_of(by, InnerPiece::Pawn)).is_empty()
+            || !(knight_attacks(square) & self.pieces_of(by, InnerPiece::Knight)).is_empty()
+            || !(king_attacks(square) & self.pieces_of(by, InnerPiece::King)).is_empty()
+            || !(rook_attacks(square, occupied) & (self.pieces_of(by, InnerPiece::Rook) | queens))
+                .is_empty()
+            || !(bishop_attacks(square, occupied)
+                & (self.pieces_of(by, InnerPiece::Bishop) | queens))
+                .is_empty()
+    }
+    pub fn pieces(&self) -> impl Iterator<Item = Piece> + '_ {
+        self.inner_board.iter().flat_map(|rank| rank.0.iter().flatten().copied())
     }
 }
-impl IndexMut<&BoardPosition> for Board{
-    fn index_mut(&mut self, index: &BoardPosition) -> &mut Self::Output {
-        &mut self[&index.rank][&index.file]
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
     }
 }
+// the board is stored from the eighth rank down, the way it is printed
+fn rank_index(rank: &Rank) -> usize {
+    (8 - i8::from(rank)) as usize
+}
+fn file_index(file: &File) -> usize {
+    (i8::from(file) - 1) as usize
+}
 impl Index<&Rank> for Board {
     type Output = BoardRank;
     fn index(&self, index: &Rank) -> &Self::Output {
         self.inner_board
-            .get(<&Rank as Into<i8>>::into(index) as usize)
+            .get(rank_index(index))
             .expect("Rank is bigger than board")
     }
 }
@@ -93,21 +299,7 @@ impl Index<&File> for BoardRank {
     type Output = Option<Piece>;
     fn index(&self, index: &File) -> &Self::Output {
         self.0
-            .get(<&File as Into<i8>>::into(index) as usize)
-            .expect("File is bigger than board")
-    }
-}
-impl IndexMut<File> for BoardRank {
-    fn index_mut(&mut self, index: File) -> &mut Self::Output {
-        self.0
-            .get_mut(<File as Into<i8>>::into(index) as usize)
-            .expect("File is bigger than board")
-    }
-}
-impl IndexMut<&File> for BoardRank {
-    fn index_mut(&mut self, index: &File) -> &mut Self::Output {
-        self.0
-            .get_mut(<&File as Into<i8>>::into(index) as usize)
+            .get(file_index(index))
             .expect("File is bigger than board")
     }
 }
@@ -115,7 +307,7 @@ impl Index<Rank> for Board {
     type Output = BoardRank;
     fn index(&self, index: Rank) -> &Self::Output {
         self.inner_board
-            .get(<Rank as Into<i8>>::into(index) as usize)
+            .get(rank_index(&index))
             .expect("Rank is bigger than board")
     }
 }
@@ -123,7 +315,7 @@ impl Index<File> for BoardRank {
     type Output = Option<Piece>;
     fn index(&self, index: File) -> &Self::Output {
         self.0
-            .get(<File as Into<i8>>::into(index) as usize)
+            .get(file_index(&index))
             .expect("File is bigger than board")
     }
 }
@@ -134,12 +326,10 @@ impl Index<&BoardPosition> for Board {
     }
 }
 
+// plain letters with coordinates, see TextRenderer for the other options
 impl Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for rank in &self.inner_board {
-            writeln!(f, "{}", rank)?;
-        }
-        Ok(())
+        write!(f, "{}", TextRenderer::default().render(self))
     }
 }
 impl Display for BoardRank {
@@ -1,4 +1,4 @@
-use std::{ops::Add, str::FromStr};
+use std::{fmt::Display, ops::Add, str::FromStr};
 
 use crate::chess_engine::{Error, errors::BoardPositionError};
 
@@ -54,3 +54,8 @@ impl Add<MoveOffset> for &BoardPosition {
         Ok(BoardPosition { file, rank })
     }
 }
+impl Display for BoardPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.file, self.rank)
+    }
+}
@@ -0,0 +1,144 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::chess_engine::{errors::FenError, pieces::Color, Error};
+
+use super::{BoardPosition, File, Rank};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastleSide {
+    Kingside,
+    Queenside,
+}
+impl CastleSide {
+    // the file the rook starts on
+    pub fn rook_file(&self) -> File {
+        match self {
+            CastleSide::Kingside => File::H,
+            CastleSide::Queenside => File::A,
+        }
+    }
+    // the files the king and rook end up on
+    pub fn king_destination(&self) -> File {
+        match self {
+            CastleSide::Kingside => File::G,
+            CastleSide::Queenside => File::C,
+        }
+    }
+    pub fn rook_destination(&self) -> File {
+        match self {
+            CastleSide::Kingside => File::F,
+            CastleSide::Queenside => File::D,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+impl CastlingRights {
+    pub fn all() -> Self {
+        CastlingRights {
+            white_kingside: true,
+            white_queenside: true,
+            black_kingside: true,
+            black_queenside: true,
+        }
+    }
+    pub fn none() -> Self {
+        CastlingRights {
+            white_kingside: false,
+            white_queenside: false,
+            black_kingside: false,
+            black_queenside: false,
+        }
+    }
+    pub fn has(&self, color: Color, side: CastleSide) -> bool {
+        match (color, side) {
+            (Color::White, CastleSide::Kingside) => self.white_kingside,
+            (Color::White, CastleSide::Queenside) => self.white_queenside,
+            (Color::Black, CastleSide::Kingside) => self.black_kingside,
+            (Color::Black, CastleSide::Queenside) => self.black_queenside,
+        }
+    }
+    pub fn remove(&mut self, color: Color, side: CastleSide) {
+        match (color, side) {
+            (Color::White, CastleSide::Kingside) => self.white_kingside = false,
+            (Color::White, CastleSide::Queenside) => self.white_queenside = false,
+            (Color::Black, CastleSide::Kingside) => self.black_kingside = false,
+            (Color::Black, CastleSide::Queenside) => self.black_queenside = false,
+        }
+    }
+    // anything moving from or to a king or rook start square ends the matching rights,
+    // this covers the king or rook moving as well as the rook getting captured
+    pub fn remove_for_square(&mut self, pos: &BoardPosition) {
+        for color in [Color::White, Color::Black] {
+            if pos.rank != home_rank(color) {
+                continue;
+            }
+            for side in [CastleSide::Kingside, CastleSide::Queenside] {
+                if pos.file == File::E || pos.file == side.rook_file() {
+                    self.remove(color, side);
+                }
+            }
+        }
+    }
+}
+pub fn home_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::One,
+        Color::Black => Rank::Eight,
+    }
+}
+impl Default for CastlingRights {
+    fn default() -> Self {
+        CastlingRights::all()
+    }
+}
+impl FromStr for CastlingRights {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rights = CastlingRights::none();
+        if s == "-" {
+            return Ok(rights);
+        }
+        for c in s.chars() {
+            let right = match c {
+                'K' => &mut rights.white_kingside,
+                'Q' => &mut rights.white_queenside,
+                'k' => &mut rights.black_kingside,
+                'q' => &mut rights.black_queenside,
+                _ => Err(FenError::InvalidCastling(s.to_string()))?,
+            };
+            if *right {
+                Err(FenError::InvalidCastling(s.to_string()))?;
+            }
+            *right = true;
+        }
+        if s.is_empty() {
+            Err(FenError::InvalidCastling(s.to_string()))?;
+        }
+        Ok(rights)
+    }
+}
+impl Display for CastlingRights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == CastlingRights::none() {
+            return write!(f, "-");
+        }
+        for (right, c) in [
+            (self.white_kingside, 'K'),
+            (self.white_queenside, 'Q'),
+            (self.black_kingside, 'k'),
+            (self.black_queenside, 'q'),
+        ] {
+            if right {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::chess_engine::{
+    errors::FenError,
+    pieces::{Color, InnerPiece, Piece},
+    Error,
+};
+
+use super::{Board, BoardPosition, CastlingRights, File, MoveOffset, Rank};
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+impl Board {
+    // the halfmove and fullmove counters are optional, a lot of test positions leave them out
+    pub fn from_fen(fen: &str) -> Result<Board, Error> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next().ok_or(FenError::MissingField("piece placement"))?;
+        let turn = fields.next().ok_or(FenError::MissingField("side to move"))?;
+        let castling = fields.next().ok_or(FenError::MissingField("castling rights"))?;
+        let en_passant = fields.next().ok_or(FenError::MissingField("en passant square"))?;
+        let halfmove = fields.next().unwrap_or("0");
+        let fullmove = fields.next().unwrap_or("1");
+        let extra = fields.count();
+        if extra > 0 {
+            Err(FenError::TooManyFields(6 + extra))?;
+        }
+
+        let mut board = Board::empty();
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            Err(FenError::WrongRankCount(ranks.len()))?;
+        }
+        for (rank, row) in Rank::ALL.iter().rev().zip(ranks) {
+            let mut files = File::ALL.iter();
+            for c in row.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    if empty == 0 {
+                        Err(FenError::InvalidRank(row.to_string()))?;
+                    }
+                    for _ in 0..empty {
+                        files.next().ok_or(FenError::InvalidRank(row.to_string()))?;
+                    }
+                    continue;
+                }
+                let piece = Piece::from_fen_char(c).ok_or(FenError::InvalidPiece(c))?;
+                let file = files.next().ok_or(FenError::InvalidRank(row.to_string()))?;
+                board.put_piece(BoardPosition::new(*file, *rank), piece);
+            }
+            if files.next().is_some() {
+                Err(FenError::InvalidRank(row.to_string()))?;
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            let kings = board.pieces_of(color, InnerPiece::King).count();
+            if kings != 1 {
+                Err(FenError::KingCount(color, kings))?;
+            }
+        }
+
+        board.turn = match turn {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => Err(FenError::InvalidTurn(turn.to_string()))?,
+        };
+        if board.is_in_check(board.turn.opposite()) {
+            Err(FenError::KingCanBeTaken(board.turn.opposite()))?;
+        }
+        board.castling_rights = castling.parse::<CastlingRights>()?;
+        board.en_passant = match en_passant {
+            "-" => None,
+            square => Some(
+                square
+                    .parse::<BoardPosition>()
+                    .map_err(|_| FenError::InvalidEnPassant(square.to_string()))?,
+            ),
+        };
+        // only right behind a pawn of the side that just moved, anything else breaks move generation
+        if let Some(square) = board.en_passant {
+            let mover = board.turn.opposite();
+            let rank = match mover {
+                Color::White => Rank::Three,
+                Color::Black => Rank::Six,
+            };
+            let pawn = (square + MoveOffset(mover.forward(), 0))
+                .ok()
+                .and_then(|pos| board[&pos])
+                .is_some_and(|piece| piece.color == mover && piece.type_of_piece() == InnerPiece::Pawn);
+            if square.rank != rank || board.has_piece(&square) || !pawn {
+                Err(FenError::InvalidEnPassant(en_passant.to_string()))?;
+            }
+        }
+        board.halfmove_clock = halfmove
+            .parse()
+            .map_err(|_| FenError::InvalidCounter(halfmove.to_string()))?;
+        board.fullmove_number = fullmove
+            .parse()
+            .map_err(|_| FenError::InvalidCounter(fullmove.to_string()))?;
+        board.reset_hash();
+        Ok(board)
+    }
+    pub fn to_fen(&self) -> String {
+        let mut placement = String::new();
+        for (i, rank) in Rank::ALL.iter().rev().enumerate() {
+            if i > 0 {
+                placement.push('/');
+            }
+            let mut empty = 0;
+            for file in File::ALL {
+                match self[&BoardPosition::new(file, *rank)] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(piece.fen_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+        }
+        let turn = match self.turn {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+        let en_passant = self
+            .en_passant
+            .map_or_else(|| "-".to_string(), |pos| pos.to_string());
+        format!(
+            "{} {} {} {} {} {}",
+            placement,
+            turn,
+            self.castling_rights,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
+    fmt::Display,
     ops::{Add, Sub},
-    str::FromStr,
 };
 
 use crate::chess_engine::{errors::BoardPositionError, Error};
@@ -16,14 +16,30 @@ pub enum File {
     G,
     H,
 }
+impl File {
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+}
 impl TryFrom<char> for File {
     type Error = Error;
     fn try_from(value: char) -> Result<Self, Self::Error> {
-        let file = value
-            .to_string()
-            .parse::<i8>()
-            .map_err(|_| BoardPositionError::NotAFile(value.to_string()))?;
-        Self::try_from(file)
+        match value {
+            'a'..='h' => Self::try_from((value as u8 - b'a') as i8 + 1),
+            _ => Err(BoardPositionError::NotAFile(value.to_string()))?,
+        }
+    }
+}
+impl From<File> for char {
+    fn from(value: File) -> char {
+        (b'a' + (i8::from(value) - 1) as u8) as char
     }
 }
 // can stil implement from_str and just ensure it is one long buut not needed
@@ -45,10 +61,10 @@ impl TryFrom<i8> for File {
         })
     }
 }
-impl Into<i8> for &File {
+impl From<&File> for i8 {
     //only implementing into for it beacuse we need checks when from i8 -> File
-    fn into(self) -> i8 {
-        match self {
+    fn from(value: &File) -> i8 {
+        match value {
             File::A => 1,
             File::B => 2,
             File::C => 3,
@@ -60,10 +76,10 @@ impl Into<i8> for &File {
         }
     }
 }
-impl Into<i8> for File {
+impl From<File> for i8 {
     //only implementing into for it beacuse we need checks when from i8 -> File
-    fn into(self) -> i8 {
-        (&self).into()
+    fn from(value: File) -> i8 {
+        (&value).into()
     }
 }
 impl Add for File {
@@ -97,3 +113,8 @@ impl Sub<i8> for File {
         (lhsv - rhs).try_into()
     }
 }
+impl Display for File {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
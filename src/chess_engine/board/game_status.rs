@@ -0,0 +1,94 @@
+use crate::chess_engine::pieces::{Color, InnerPiece};
+
+use super::{Bitboard, Board};
+
+// the light squares, a1 is dark
+const LIGHT_SQUARES: Bitboard = Bitboard(0x55AA_55AA_55AA_55AA);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate { winner: Color },
+    Stalemate,
+    // the same position for the third time, either player may claim a draw
+    ThreefoldRepetition,
+    // a hundred halfmoves without a capture or pawn move, either player may claim a draw
+    FiftyMoveRule,
+    // the draws below end the game without anyone having to claim them
+    SeventyFiveMoveRule,
+    FivefoldRepetition,
+    InsufficientMaterial,
+}
+impl GameStatus {
+    pub fn is_over(&self) -> bool {
+        *self != GameStatus::Ongoing
+    }
+    pub fn is_draw(&self) -> bool {
+        !matches!(self, GameStatus::Ongoing | GameStatus::Checkmate { .. })
+    }
+    pub fn winner(&self) -> Option<Color> {
+        match self {
+            GameStatus::Checkmate { winner } => Some(*winner),
+            _ => None,
+        }
+    }
+}
+
+impl Board {
+    // mate and stalemate come first, a move that mates still wins even if it is the hundredth
+    pub fn game_status(&self) -> GameStatus {
+        if self.legal_moves().is_empty() {
+            return match self.is_in_check(self.turn) {
+                true => GameStatus::Checkmate {
+                    winner: self.turn.opposite(),
+                },
+                false => GameStatus::Stalemate,
+            };
+        }
+        let repetitions = self.repetitions();
+        match self.halfmove_clock {
+            _ if self.is_insufficient_material() => GameStatus::InsufficientMaterial,
+            _ if repetitions >= 5 => GameStatus::FivefoldRepetition,
+            clock if clock >= 150 => GameStatus::SeventyFiveMoveRule,
+            _ if repetitions >= 3 => GameStatus::ThreefoldRepetition,
+            clock if clock >= 100 => GameStatus::FiftyMoveRule,
+            _ => GameStatus::Ongoing,
+        }
+    }
+    // neither side can mate with whatever is left: lone kings, a single minor piece, or only
+    // bishops that all stand on the same colour
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy = [InnerPiece::Pawn, InnerPiece::Rook, InnerPiece::Queen];
+        let any_of = |kind: InnerPiece| {
+            !(self.pieces_of(Color::White, kind) | self.pieces_of(Color::Black, kind)).is_empty()
+        };
+        if heavy.into_iter().any(any_of) {
+            return false;
+        }
+        let knights = self.pieces_of(Color::White, InnerPiece::Knight)
+            | self.pieces_of(Color::Black, InnerPiece::Knight);
+        let bishops = self.pieces_of(Color::White, InnerPiece::Bishop)
+            | self.pieces_of(Color::Black, InnerPiece::Bishop);
+        if knights.count() + bishops.count() <= 1 {
+            return true;
+        }
+        knights.is_empty() && ((bishops & LIGHT_SQUARES).is_empty() || (bishops & !LIGHT_SQUARES).is_empty())
+    }
+    // whether `color` has anything to mate with, what decides a flag fall between a win and a draw.
+    // a bare king can't, and neither can a lone minor piece against a bare king. when the whole
+    // board is insufficient material nobody can, whatever is left
+    pub fn can_checkmate(&self, color: Color) -> bool {
+        if self.is_insufficient_material() {
+            return false;
+        }
+        let own = self.occupancy(color);
+        let kings = self.pieces_of(color, InnerPiece::King);
+        let minors = self.pieces_of(color, InnerPiece::Knight) | self.pieces_of(color, InnerPiece::Bishop);
+        let other_alone = self.occupancy(color.opposite()).count() == 1;
+        match own.count() {
+            1 => false,
+            2 => !(other_alone && (own ^ kings) == minors),
+            _ => true,
+        }
+    }
+}
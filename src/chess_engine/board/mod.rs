@@ -1,10 +1,27 @@
+mod attacks;
+mod bitboard;
+#[allow(clippy::module_inception)]
 mod board;
 mod board_position;
+mod castling_rights;
+mod fen;
 mod file;
+mod game_status;
+mod perft;
 mod rank;
+mod san;
+mod uci_move;
+mod zobrist;
+pub use attacks::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+};
+pub use bitboard::Bitboard;
 pub use board::Board;
 pub use board_position::BoardPosition;
+pub use castling_rights::{home_rank, CastleSide, CastlingRights};
+pub use fen::STARTING_FEN;
 pub use file::File;
+pub use game_status::GameStatus;
 pub use rank::Rank;
 #[derive(Clone, Copy)]
 pub struct MoveOffset(pub i8, pub i8);
@@ -0,0 +1,40 @@
+use crate::chess_engine::pieces::Action;
+
+use super::Board;
+
+impl Board {
+    // counts the leaf nodes of the legal move tree, the board is left as it was
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves
+            .into_iter()
+            .map(|mut action| {
+                action.execute(self);
+                let nodes = self.perft(depth - 1);
+                action.undo(self);
+                nodes
+            })
+            .sum()
+    }
+    // the node count below every root move, handy for diffing against other engines
+    pub fn perft_divide(&mut self, depth: u32) -> Vec<(Action, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        self.legal_moves()
+            .into_iter()
+            .map(|mut action| {
+                action.execute(self);
+                let nodes = self.perft(depth - 1);
+                action.undo(self);
+                (action, nodes)
+            })
+            .collect()
+    }
+}
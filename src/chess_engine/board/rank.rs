@@ -1,4 +1,4 @@
-use std::{ops::{Add, Sub}, str::FromStr};
+use std::{fmt::Display, ops::{Add, Sub}};
 
 use crate::chess_engine::{Error, errors::BoardPositionError};
 
@@ -13,20 +13,32 @@ pub enum Rank {
     Seven,
     Eight,
 }
+impl Rank {
+    pub const ALL: [Rank; 8] = [
+        Rank::One,
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+    ];
+}
 impl TryFrom<char> for Rank {
     type Error = Error;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         let rank = value
             .to_string()
             .parse::<i8>()
-            .map_err(|_| BoardPositionError::NotAFile(value.to_string()))?;
+            .map_err(|_| BoardPositionError::NotARank(value.to_string()))?;
         Self::try_from(rank)
     }
 }
-impl Into<i8> for &Rank {
+impl From<&Rank> for i8 {
     //only implementing into for it beacuse we need checks when from i8 -> File
-    fn into(self) -> i8 {
-        match self {
+    fn from(value: &Rank) -> i8 {
+        match value {
             Rank::One => 1,
             Rank::Two => 2,
             Rank::Three => 3,
@@ -38,10 +50,10 @@ impl Into<i8> for &Rank {
         }
     }
 }
-impl Into<i8> for Rank {
+impl From<Rank> for i8 {
     //only implementing into for it beacuse we need checks when from i8 -> File
-    fn into(self) -> i8 {
-        (&self).into()
+    fn from(value: Rank) -> i8 {
+        (&value).into()
     }
 }
 impl TryFrom<i8> for Rank {
@@ -91,3 +103,8 @@ impl Sub<i8> for Rank {
         (lhsv - rhs).try_into()
     }
 }
+impl Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", i8::from(self))
+    }
+}
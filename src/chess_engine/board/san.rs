@@ -0,0 +1,160 @@
+use crate::chess_engine::{
+    errors::SanError,
+    pieces::{Action, InnerPiece, Piece},
+    Error,
+};
+
+use super::{Board, BoardPosition, CastleSide, File, Rank};
+
+impl Action {
+    // standard algebraic notation like Nbd7, exd5, O-O or e8=Q+, `board` is the position before
+    // the move is played
+    pub fn to_san(&self, board: &Board) -> String {
+        let mut san = match self.castle_side() {
+            Some(CastleSide::Kingside) => "O-O".to_string(),
+            Some(CastleSide::Queenside) => "O-O-O".to_string(),
+            None => self.san_body(board),
+        };
+        let mut after = board.clone();
+        self.clone().execute(&mut after);
+        if after.is_in_check(after.turn()) {
+            san.push(match after.legal_moves().is_empty() {
+                true => '#',
+                false => '+',
+            });
+        }
+        san
+    }
+    fn san_body(&self, board: &Board) -> String {
+        let kind = board[&self.piece_pos]
+            .expect("the piece to move is on the board")
+            .type_of_piece();
+        let mut san = String::new();
+        match kind {
+            // pawns only ever need their file, and only when capturing
+            InnerPiece::Pawn => {
+                if self.is_capture() {
+                    san.push(char::from(self.piece_pos.file));
+                }
+            }
+            kind => {
+                san.push(kind.fen_char().to_ascii_uppercase());
+                let rivals: Vec<BoardPosition> = board
+                    .legal_moves()
+                    .iter()
+                    .filter(|other| {
+                        other.to_pos == self.to_pos
+                            && other.piece_pos != self.piece_pos
+                            && other.castle_side().is_none()
+                            && board[&other.piece_pos].is_some_and(|p| p.type_of_piece() == kind)
+                    })
+                    .map(|other| other.piece_pos)
+                    .collect();
+                // the file if that tells them apart, then the rank, and both as a last resort
+                if !rivals.is_empty() {
+                    let same_file = rivals.iter().any(|pos| pos.file == self.piece_pos.file);
+                    let same_rank = rivals.iter().any(|pos| pos.rank == self.piece_pos.rank);
+                    if !same_file {
+                        san.push(char::from(self.piece_pos.file));
+                    } else if !same_rank {
+                        san.push_str(&i8::from(self.piece_pos.rank).to_string());
+                    } else {
+                        san.push_str(&self.piece_pos.to_string());
+                    }
+                }
+            }
+        }
+        if self.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&self.to_pos.to_string());
+        if let Some(piece) = self.promotion() {
+            san.push('=');
+            san.push(piece.fen_char().to_ascii_uppercase());
+        }
+        san
+    }
+}
+
+impl Board {
+    // finds the legal move a piece of SAN stands for, check marks and annotations like !? are
+    // ignored and a missing x on a capture is forgiven
+    pub fn parse_san(&self, san: &str) -> Result<Action, Error> {
+        let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        if text.is_empty() {
+            Err(SanError::Empty)?;
+        }
+        let invalid = || SanError::InvalidSan(san.to_string());
+        let castle = match text {
+            "O-O" | "0-0" => Some(CastleSide::Kingside),
+            "O-O-O" | "0-0-0" => Some(CastleSide::Queenside),
+            _ => None,
+        };
+        if let Some(side) = castle {
+            return self
+                .legal_moves()
+                .iter()
+                .find(|action| action.castle_side() == Some(side))
+                .cloned()
+                .ok_or_else(|| SanError::IllegalMove(san.to_string()).into());
+        }
+
+        let mut chars: Vec<char> = text.chars().filter(|c| *c != 'x').collect();
+        let kind = match chars.first() {
+            Some(c) if "KQRBN".contains(*c) => {
+                let kind = kind_of(*c).ok_or_else(invalid)?;
+                chars.remove(0);
+                kind
+            }
+            _ => InnerPiece::Pawn,
+        };
+        // e8=Q, though e8Q shows up often enough to accept it too
+        let promotion = match chars.last() {
+            Some(c) if kind == InnerPiece::Pawn && "QRBN".contains(*c) => {
+                let promotion = kind_of(*c).ok_or_else(invalid)?;
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(promotion)
+            }
+            _ => None,
+        };
+        if chars.len() < 2 || chars.len() > 4 {
+            Err(invalid())?;
+        }
+        let to: String = chars[chars.len() - 2..].iter().collect();
+        let to_pos = to.parse::<BoardPosition>().map_err(|_| invalid())?;
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in &chars[..chars.len() - 2] {
+            match c {
+                'a'..='h' => from_file = Some(File::try_from(*c).map_err(|_| invalid())?),
+                '1'..='8' => from_rank = Some(Rank::try_from(*c).map_err(|_| invalid())?),
+                _ => Err(invalid())?,
+            }
+        }
+        // a pawn move without a file is a push, so it comes from the file it lands on
+        if kind == InnerPiece::Pawn && from_file.is_none() {
+            from_file = Some(to_pos.file);
+        }
+
+        let mut candidates = self.legal_moves().into_iter().filter(|action| {
+            action.to_pos == to_pos
+                && action.castle_side().is_none()
+                && action.promotion() == promotion
+                && from_file.is_none_or(|file| action.piece_pos.file == file)
+                && from_rank.is_none_or(|rank| action.piece_pos.rank == rank)
+                && self[&action.piece_pos].is_some_and(|p| p.type_of_piece() == kind)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(action), None) => Ok(action),
+            (None, _) => Err(SanError::IllegalMove(san.to_string()))?,
+            (Some(_), Some(_)) => Err(SanError::AmbiguousMove(san.to_string()))?,
+        }
+    }
+}
+
+fn kind_of(c: char) -> Option<InnerPiece> {
+    Piece::from_fen_char(c).map(|piece| piece.type_of_piece())
+}
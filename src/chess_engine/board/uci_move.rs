@@ -0,0 +1,41 @@
+use crate::chess_engine::{
+    errors::UciMoveError,
+    pieces::{Action, InnerPiece, Piece},
+    Error,
+};
+
+use super::{Board, BoardPosition};
+
+impl Action {
+    // long algebraic notation the way uci writes it, e2e4, e7e8q, and the king move for castling
+    pub fn to_uci(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Board {
+    pub fn parse_uci_move(&self, text: &str) -> Result<Action, Error> {
+        let invalid = || UciMoveError::InvalidMove(text.to_string());
+        if !text.is_ascii() || !(4..=5).contains(&text.len()) {
+            Err(invalid())?;
+        }
+        let from = text[0..2].parse::<BoardPosition>().map_err(|_| invalid())?;
+        let to = text[2..4].parse::<BoardPosition>().map_err(|_| invalid())?;
+        let promotion = match text[4..].chars().next() {
+            Some(c) => {
+                let piece = Piece::from_fen_char(c).map(|piece| piece.type_of_piece());
+                match piece.filter(|piece| InnerPiece::PROMOTIONS.contains(piece)) {
+                    Some(piece) => Some(piece),
+                    None => Err(invalid())?,
+                }
+            }
+            None => None,
+        };
+        self.legal_moves()
+            .into_iter()
+            .find(|action| {
+                action.piece_pos == from && action.to_pos == to && action.promotion() == promotion
+            })
+            .ok_or_else(|| UciMoveError::IllegalMove(text.to_string()).into())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::chess_engine::pieces::{Color, InnerPiece};
+
+use super::{attacks::pawn_attacks, Board, BoardPosition, CastlingRights, Rank};
+
+// random numbers for every feature of a position, a hash is the xor of everything present
+struct ZobristKeys {
+    pieces: [[[u64; 64]; 6]; 2],
+    black_to_move: u64,
+    castling: [u64; 4],
+    en_passant_file: [u64; 8],
+}
+
+static KEYS: ZobristKeys = generate_keys();
+
+// splitmix64, fixed seed so hashes stay the same between runs
+const fn next_key(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+const fn generate_keys() -> ZobristKeys {
+    let mut state = 0x2545_F491_4F6C_DD1D;
+    let mut pieces = [[[0; 64]; 6]; 2];
+    let mut color = 0;
+    while color < 2 {
+        let mut piece = 0;
+        while piece < 6 {
+            let mut square = 0;
+            while square < 64 {
+                pieces[color][piece][square] = next_key(&mut state);
+                square += 1;
+            }
+            piece += 1;
+        }
+        color += 1;
+    }
+    let black_to_move = next_key(&mut state);
+    let mut castling = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        castling[i] = next_key(&mut state);
+        i += 1;
+    }
+    let mut en_passant_file = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        en_passant_file[i] = next_key(&mut state);
+        i += 1;
+    }
+    ZobristKeys {
+        pieces,
+        black_to_move,
+        castling,
+        en_passant_file,
+    }
+}
+
+pub(super) fn piece_key(color: Color, piece: InnerPiece, square: u8) -> u64 {
+    KEYS.pieces[color.index()][piece.index()][square as usize]
+}
+pub(super) fn side_key() -> u64 {
+    KEYS.black_to_move
+}
+pub(super) fn castling_key(rights: &CastlingRights) -> u64 {
+    [
+        rights.white_kingside,
+        rights.white_queenside,
+        rights.black_kingside,
+        rights.black_queenside,
+    ]
+    .iter()
+    .zip(KEYS.castling)
+    .filter(|(right, _)| **right)
+    .fold(0, |hash, (_, key)| hash ^ key)
+}
+impl Board {
+    // the hash from scratch, the board keeps its own copy up to date as moves are played
+    pub(super) fn compute_hash(&self) -> u64 {
+        let pieces = self.pieces().fold(0, |hash, piece| {
+            let square = piece.pos.expect("pieces on the board know their square").square();
+            hash ^ piece_key(piece.color, piece.type_of_piece(), square)
+        });
+        let side = match self.turn {
+            Color::White => 0,
+            Color::Black => side_key(),
+        };
+        pieces ^ side ^ castling_key(&self.castling_rights) ^ self.en_passant_key(self.en_passant)
+    }
+    // the en passant file only counts when a pawn could take there, otherwise the position is
+    // the same as the one without the square and has to repeat with it
+    pub(super) fn en_passant_key(&self, en_passant: Option<BoardPosition>) -> u64 {
+        let Some(pos) = en_passant else {
+            return 0;
+        };
+        // the square is behind the pawn that just moved, so its rank says who could take
+        let capturer = match pos.rank {
+            Rank::Three => Color::Black,
+            _ => Color::White,
+        };
+        let attackers = pawn_attacks(capturer.opposite(), pos.square())
+            & self.pieces_of(capturer, InnerPiece::Pawn);
+        match attackers.is_empty() {
+            true => 0,
+            false => KEYS.en_passant_file[(i8::from(pos.file) - 1) as usize],
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::{errors::ClockError, pieces::Color, Error};
+
+// where the clock gets the time from, only differences between two readings matter
+pub trait TimeSource: Debug + Send + Sync {
+    fn now(&self) -> Duration;
+}
+
+#[derive(Debug)]
+pub struct WallClock(Instant);
+impl WallClock {
+    pub fn new() -> WallClock {
+        WallClock(Instant::now())
+    }
+}
+impl Default for WallClock {
+    fn default() -> Self {
+        WallClock::new()
+    }
+}
+impl TimeSource for WallClock {
+    fn now(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+// time that only moves when told to, clones share the same time so tests can keep one around
+#[derive(Debug, Clone, Default)]
+pub struct ManualTime(Arc<Mutex<Duration>>);
+impl ManualTime {
+    pub fn new() -> ManualTime {
+        ManualTime::default()
+    }
+    pub fn advance(&self, by: Duration) {
+        *self.0.lock().expect("nobody panics while holding the time") += by;
+    }
+}
+impl TimeSource for ManualTime {
+    fn now(&self) -> Duration {
+        *self.0.lock().expect("nobody panics while holding the time")
+    }
+}
+
+// what happens to the clock around every move
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Delay {
+    #[default]
+    None,
+    // added after every move
+    Fischer(Duration),
+    // the time used is given back after the move, but never more than this
+    Bronstein(Duration),
+    // the clock waits this long before it starts counting down
+    Simple(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    // how many moves have to be made in this stage, None for the rest of the game
+    pub moves: Option<u32>,
+    pub time: Duration,
+    pub delay: Delay,
+}
+
+// one or more stages, a stage with a move count is repeated if it is the last one
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeControl {
+    stages: Vec<Stage>,
+}
+impl TimeControl {
+    pub fn new(stages: Vec<Stage>) -> Result<TimeControl, Error> {
+        if stages.is_empty() {
+            Err(ClockError::NoStages)?;
+        }
+        Ok(TimeControl { stages })
+    }
+    fn single(time: Duration, delay: Delay) -> TimeControl {
+        TimeControl {
+            stages: vec![Stage {
+                moves: None,
+                time,
+                delay,
+            }],
+        }
+    }
+    pub fn sudden_death(time: Duration) -> TimeControl {
+        TimeControl::single(time, Delay::None)
+    }
+    pub fn fischer(time: Duration, increment: Duration) -> TimeControl {
+        TimeControl::single(time, Delay::Fischer(increment))
+    }
+    pub fn bronstein(time: Duration, delay: Duration) -> TimeControl {
+        TimeControl::single(time, Delay::Bronstein(delay))
+    }
+    pub fn simple_delay(time: Duration, delay: Duration) -> TimeControl {
+        TimeControl::single(time, Delay::Simple(delay))
+    }
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+    fn stage(&self, index: usize) -> &Stage {
+        &self.stages[index.min(self.stages.len() - 1)]
+    }
+}
+// the pgn TimeControl format in seconds, 40/5400+30:1800+30 is 40 moves in 90 minutes and then
+// 30 minutes for the rest, with 30 seconds added every move
+impl FromStr for TimeControl {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ClockError::InvalidTimeControl(s.to_string());
+        let seconds = |text: &str| text.parse::<u64>().map(Duration::from_secs).map_err(|_| invalid());
+        let stages = s
+            .split(':')
+            .map(|stage| {
+                let (moves, rest) = match stage.split_once('/') {
+                    Some((moves, rest)) => (Some(moves.parse::<u32>().map_err(|_| invalid())?), rest),
+                    None => (None, stage),
+                };
+                let (time, delay) = match rest.split_once('+') {
+                    Some((time, increment)) => (seconds(time)?, Delay::Fischer(seconds(increment)?)),
+                    None => (seconds(rest)?, Delay::None),
+                };
+                Ok(Stage { moves, time, delay })
+            })
+            .collect::<Result<Vec<_>, ClockError>>()?;
+        TimeControl::new(stages)
+    }
+}
+// only fischer increments can be written this way, other delays are left out
+impl Display for TimeControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                write!(f, ":")?;
+            }
+            if let Some(moves) = stage.moves {
+                write!(f, "{}/", moves)?;
+            }
+            write!(f, "{}", stage.time.as_secs())?;
+            if let Delay::Fischer(increment) = stage.delay {
+                write!(f, "+{}", increment.as_secs())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock {
+    control: TimeControl,
+    // indexed by Color::index like everything else
+    remaining: [Duration; 2],
+    stage: [usize; 2],
+    moves_in_stage: [u32; 2],
+    // whose clock is running and when it was started
+    running: Option<(Color, Duration)>,
+    source: Arc<dyn TimeSource>,
+}
+impl Clock {
+    pub fn new(control: TimeControl) -> Clock {
+        Clock::with_time_source(control, Arc::new(WallClock::new()))
+    }
+    pub fn with_time_source(control: TimeControl, source: Arc<dyn TimeSource>) -> Clock {
+        let time = control.stage(0).time;
+        Clock {
+            control,
+            remaining: [time; 2],
+            stage: [0; 2],
+            moves_in_stage: [0; 2],
+            running: None,
+            source,
+        }
+    }
+    pub fn time_control(&self) -> &TimeControl {
+        &self.control
+    }
+    pub fn running(&self) -> Option<Color> {
+        self.running.map(|(color, _)| color)
+    }
+    pub fn start(&mut self, color: Color) {
+        self.stop();
+        self.running = Some((color, self.source.now()));
+    }
+    // stops whichever clock is running, keeping the time it used
+    pub fn stop(&mut self) {
+        if let Some((color, _)) = self.running {
+            self.remaining[color.index()] = self.remaining(color);
+            self.running = None;
+        }
+    }
+    // the time `color` has left, counting the move being thought about right now
+    pub fn remaining(&self, color: Color) -> Duration {
+        let remaining = self.remaining[color.index()];
+        match self.running {
+            Some((running, started)) if running == color => {
+                let elapsed = self.source.now().saturating_sub(started);
+                let used = match self.current_stage(color).delay {
+                    Delay::Simple(delay) => elapsed.saturating_sub(delay),
+                    _ => elapsed,
+                };
+                remaining.saturating_sub(used)
+            }
+            _ => remaining,
+        }
+    }
+    // the side whose time ran out, only the running clock can fall
+    pub fn flagged(&self) -> Option<Color> {
+        self.running().filter(|color| self.remaining(*color).is_zero())
+    }
+    // ends the move of whoever is running and starts the other clock, returns the flagged color
+    // instead if the move came too late
+    pub fn press(&mut self) -> Result<(), Color> {
+        let Some((color, started)) = self.running else {
+            return Ok(());
+        };
+        if let Some(flagged) = self.flagged() {
+            return Err(flagged);
+        }
+        let elapsed = self.source.now().saturating_sub(started);
+        let stage = *self.current_stage(color);
+        self.stop();
+        let index = color.index();
+        self.remaining[index] += match stage.delay {
+            Delay::Fischer(increment) => increment,
+            Delay::Bronstein(delay) => elapsed.min(delay),
+            Delay::None | Delay::Simple(_) => Duration::ZERO,
+        };
+        self.moves_in_stage[index] += 1;
+        if stage.moves == Some(self.moves_in_stage[index]) {
+            self.stage[index] += 1;
+            self.moves_in_stage[index] = 0;
+            self.remaining[index] += self.current_stage(color).time;
+        }
+        self.start(color.opposite());
+        Ok(())
+    }
+    fn current_stage(&self, color: Color) -> &Stage {
+        self.control.stage(self.stage[color.index()])
+    }
+}
@@ -1,9 +1,17 @@
-use std::{error::Error as StdError, fmt::Display};
+use std::{error::Error as StdError, fmt::Display, io};
+
+use super::pieces::Color;
 
 #[derive(Debug)]
 pub enum Error{
     Action(ActionError),
-    BoardPosition(BoardPositionError)
+    BoardPosition(BoardPositionError),
+    Fen(FenError),
+    San(SanError),
+    UciMove(UciMoveError),
+    Pgn(PgnError),
+    Game(GameError),
+    Clock(ClockError),
 }
 #[derive(Debug)]
 pub enum ActionError{
@@ -17,6 +25,69 @@ pub enum BoardPositionError{
     NotAFile(String),
     NotARank(String),
 }
+#[derive(Debug)]
+pub enum FenError{
+    MissingField(&'static str),
+    TooManyFields(usize),
+    InvalidPiece(char),
+    InvalidRank(String),
+    WrongRankCount(usize),
+    InvalidTurn(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidCounter(String),
+    // every side needs exactly one king
+    KingCount(Color, usize),
+    // the side that just moved left its king in check, the next move would take it
+    KingCanBeTaken(Color),
+}
+#[derive(Debug)]
+pub enum SanError{
+    Empty,
+    InvalidSan(String),
+    IllegalMove(String),
+    AmbiguousMove(String),
+}
+#[derive(Debug)]
+pub enum UciMoveError{
+    InvalidMove(String),
+    IllegalMove(String),
+}
+// where in the pgn things went wrong, both counted from 1
+#[derive(Debug)]
+pub struct PgnError{
+    pub line:usize,
+    pub column:usize,
+    pub kind:PgnErrorKind,
+}
+#[derive(Debug)]
+pub enum PgnErrorKind{
+    Io(io::Error),
+    UnexpectedChar(char),
+    InvalidTag,
+    UnterminatedTag,
+    UnterminatedComment,
+    UnterminatedVariation,
+    UnbalancedVariation,
+    EmptyVariation,
+    VariationWithoutMove,
+    NagWithoutMove,
+    InvalidNag(String),
+    ResultInVariation,
+    IllegalMove(String),
+}
+#[derive(Debug)]
+pub enum GameError{
+    GameOver,
+    IllegalMove(String),
+    NoDrawOffer,
+    NoDrawToClaim,
+}
+#[derive(Debug)]
+pub enum ClockError{
+    NoStages,
+    InvalidTimeControl(String),
+}
 impl From<ActionError> for Error{
     fn from(value: ActionError) -> Self {
         Error::Action(value)
@@ -27,9 +98,155 @@ impl From<BoardPositionError> for Error{
         Error::BoardPosition(value)
     }
 }
+impl From<FenError> for Error{
+    fn from(value: FenError) -> Self {
+        Error::Fen(value)
+    }
+}
+impl From<SanError> for Error{
+    fn from(value: SanError) -> Self {
+        Error::San(value)
+    }
+}
+impl From<UciMoveError> for Error{
+    fn from(value: UciMoveError) -> Self {
+        Error::UciMove(value)
+    }
+}
+impl From<PgnError> for Error{
+    fn from(value: PgnError) -> Self {
+        Error::Pgn(value)
+    }
+}
+impl From<GameError> for Error{
+    fn from(value: GameError) -> Self {
+        Error::Game(value)
+    }
+}
+impl From<ClockError> for Error{
+    fn from(value: ClockError) -> Self {
+        Error::Clock(value)
+    }
+}
 impl Display for Error{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f,"fuck you")
+        match self {
+            Error::Action(err)=>write!(f,"{}",err),
+            Error::BoardPosition(err)=>write!(f,"{}",err),
+            Error::Fen(err)=>write!(f,"invalid fen, {}",err),
+            Error::San(err)=>write!(f,"{}",err),
+            Error::UciMove(err)=>write!(f,"{}",err),
+            Error::Pgn(err)=>write!(f,"{}",err),
+            Error::Game(err)=>write!(f,"{}",err),
+            Error::Clock(err)=>write!(f,"{}",err),
+        }
+    }
+}
+impl Display for ActionError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionError::PieceNotInPlay=>write!(f,"the piece is not on the board"),
+            ActionError::SameColor=>write!(f,"a piece can't take one of its own colour"),
+        }
+    }
+}
+impl Display for BoardPositionError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardPositionError::CharOverflow=>write!(f,"a square is written with two characters, got more"),
+            BoardPositionError::CharUnderflow=>write!(f,"a square is written with two characters, got fewer"),
+            BoardPositionError::NotAFile(file)=>write!(f,"`{}` is not a file",file),
+            BoardPositionError::NotARank(rank)=>write!(f,"`{}` is not a rank",rank),
+        }
+    }
+}
+impl Display for FenError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::MissingField(field)=>write!(f,"the {} field is missing",field),
+            FenError::TooManyFields(count)=>write!(f,"{} fields where there are at most 6",count),
+            FenError::InvalidPiece(piece)=>write!(f,"`{}` is not a piece",piece),
+            FenError::InvalidRank(rank)=>write!(f,"the rank `{}` doesn't add up to 8 squares",rank),
+            FenError::WrongRankCount(count)=>write!(f,"{} ranks instead of 8",count),
+            FenError::InvalidTurn(turn)=>write!(f,"`{}` is not a side to move, use w or b",turn),
+            FenError::InvalidCastling(castling)=>write!(f,"invalid castling rights `{}`",castling),
+            FenError::InvalidEnPassant(square)=>write!(f,"`{}` can't be the en passant square here",square),
+            FenError::InvalidCounter(counter)=>write!(f,"invalid move counter `{}`",counter),
+            FenError::KingCount(color,count)=>{
+                let color=match color {
+                    Color::White=>"white",
+                    Color::Black=>"black",
+                };
+                write!(f,"{} has {} kings instead of 1",color,count)
+            }
+            FenError::KingCanBeTaken(color)=>{
+                let color=match color {
+                    Color::White=>"white",
+                    Color::Black=>"black",
+                };
+                write!(f,"the {} king is in check but it isn't {} to move",color,color)
+            }
+        }
+    }
+}
+impl Display for SanError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanError::Empty=>write!(f,"the move is empty"),
+            SanError::InvalidSan(san)=>write!(f,"`{}` is not a move in SAN",san),
+            SanError::IllegalMove(san)=>write!(f,"`{}` is not a legal move here",san),
+            SanError::AmbiguousMove(san)=>write!(f,"`{}` could be more than one move",san),
+        }
+    }
+}
+impl Display for UciMoveError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UciMoveError::InvalidMove(text)=>write!(f,"`{}` is not a move in coordinate notation",text),
+            UciMoveError::IllegalMove(text)=>write!(f,"`{}` is not a legal move here",text),
+        }
+    }
+}
+impl Display for PgnError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f,"line {}, column {}: {}",self.line,self.column,self.kind)
+    }
+}
+impl Display for PgnErrorKind{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnErrorKind::Io(err)=>write!(f,"{}",err),
+            PgnErrorKind::UnexpectedChar(c)=>write!(f,"unexpected `{}`",c),
+            PgnErrorKind::InvalidTag=>write!(f,"invalid tag"),
+            PgnErrorKind::UnterminatedTag=>write!(f,"tag without a closing `]`"),
+            PgnErrorKind::UnterminatedComment=>write!(f,"comment without a closing `}}`"),
+            PgnErrorKind::UnterminatedVariation=>write!(f,"variation without a closing `)`"),
+            PgnErrorKind::UnbalancedVariation=>write!(f,"`)` without a variation to close"),
+            PgnErrorKind::EmptyVariation=>write!(f,"variation without any moves"),
+            PgnErrorKind::VariationWithoutMove=>write!(f,"variation before any move"),
+            PgnErrorKind::NagWithoutMove=>write!(f,"NAG before any move"),
+            PgnErrorKind::InvalidNag(nag)=>write!(f,"invalid NAG `${}`",nag),
+            PgnErrorKind::ResultInVariation=>write!(f,"game result inside a variation"),
+            PgnErrorKind::IllegalMove(san)=>write!(f,"illegal move `{}`",san),
+        }
+    }
+}
+impl Display for GameError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::GameOver=>write!(f,"the game is already over"),
+            GameError::IllegalMove(text)=>write!(f,"`{}` is not a legal move here",text),
+            GameError::NoDrawOffer=>write!(f,"there is no draw offer to answer"),
+            GameError::NoDrawToClaim=>write!(f,"there is no draw to claim"),
+        }
+    }
+}
+impl Display for ClockError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockError::NoStages=>write!(f,"a time control needs at least one stage"),
+            ClockError::InvalidTimeControl(text)=>write!(f,"invalid time control `{}`",text),
+        }
     }
 }
 impl StdError for Error{}
\ No newline at end of file
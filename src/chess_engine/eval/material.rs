@@ -0,0 +1,37 @@
+use crate::chess_engine::{
+    board::Board,
+    pieces::{Color, InnerPiece},
+};
+
+use super::{EvalTerm, TaperedScore};
+
+pub struct Material;
+
+pub fn material_value(piece: InnerPiece) -> TaperedScore {
+    match piece {
+        InnerPiece::Pawn => TaperedScore::new(82, 94),
+        InnerPiece::Knight => TaperedScore::new(337, 281),
+        InnerPiece::Bishop => TaperedScore::new(365, 297),
+        InnerPiece::Rook => TaperedScore::new(477, 512),
+        InnerPiece::Queen => TaperedScore::new(1025, 936),
+        InnerPiece::King => TaperedScore::new(0, 0),
+    }
+}
+// a single number for a piece, good enough for things like move ordering
+pub fn piece_value(piece: InnerPiece) -> i32 {
+    material_value(piece).mg
+}
+
+impl EvalTerm for Material {
+    fn name(&self) -> &'static str {
+        "material"
+    }
+    fn evaluate(&self, board: &Board) -> TaperedScore {
+        board
+            .pieces()
+            .fold(TaperedScore::default(), |acc, piece| match piece.color {
+                Color::White => acc + material_value(piece.type_of_piece()),
+                Color::Black => acc - material_value(piece.type_of_piece()),
+            })
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::{Add, AddAssign, Neg, Sub};
+
+use super::{
+    board::Board,
+    pieces::{Color, InnerPiece},
+};
+mod material;
+mod pst;
+pub use material::{material_value, piece_value, Material};
+pub use pst::PieceSquareTables;
+
+// the phase of a position with every piece still on the board, pawns and kings don't count
+pub const MAX_PHASE: i32 = 24;
+
+// a middlegame and an endgame score, blended by how much material is left
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TaperedScore {
+    pub mg: i32,
+    pub eg: i32,
+}
+impl TaperedScore {
+    pub fn new(mg: i32, eg: i32) -> Self {
+        TaperedScore { mg, eg }
+    }
+    pub fn taper(&self, phase: i32) -> i32 {
+        let phase = phase.clamp(0, MAX_PHASE);
+        (self.mg * phase + self.eg * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+}
+impl Add for TaperedScore {
+    type Output = TaperedScore;
+    fn add(self, rhs: Self) -> Self::Output {
+        TaperedScore::new(self.mg + rhs.mg, self.eg + rhs.eg)
+    }
+}
+impl AddAssign for TaperedScore {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl Sub for TaperedScore {
+    type Output = TaperedScore;
+    fn sub(self, rhs: Self) -> Self::Output {
+        TaperedScore::new(self.mg - rhs.mg, self.eg - rhs.eg)
+    }
+}
+impl Neg for TaperedScore {
+    type Output = TaperedScore;
+    fn neg(self) -> Self::Output {
+        TaperedScore::new(-self.mg, -self.eg)
+    }
+}
+
+// one part of the evaluation, always scored from white's point of view
+pub trait EvalTerm: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn evaluate(&self, board: &Board) -> TaperedScore;
+}
+
+pub struct Evaluator {
+    terms: Vec<Box<dyn EvalTerm>>,
+}
+impl Evaluator {
+    // no terms at all, everything scores as equal
+    pub fn empty() -> Evaluator {
+        Evaluator { terms: Vec::new() }
+    }
+    pub fn add_term(&mut self, term: impl EvalTerm + 'static) {
+        self.terms.push(Box::new(term));
+    }
+    // centipawns from the point of view of the side to move
+    pub fn evaluate(&self, board: &Board) -> i32 {
+        let score = self
+            .terms
+            .iter()
+            .fold(TaperedScore::default(), |acc, term| acc + term.evaluate(board))
+            .taper(phase(board));
+        match board.turn() {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+    // every term on its own from white's point of view, for showing where a score comes from
+    pub fn breakdown(&self, board: &Board) -> Vec<(&'static str, TaperedScore)> {
+        self.terms
+            .iter()
+            .map(|term| (term.name(), term.evaluate(board)))
+            .collect()
+    }
+}
+impl Default for Evaluator {
+    fn default() -> Self {
+        let mut evaluator = Evaluator::empty();
+        evaluator.add_term(Material);
+        evaluator.add_term(PieceSquareTables);
+        evaluator
+    }
+}
+
+// MAX_PHASE in the opening going down to 0 once only kings and pawns are left
+pub fn phase(board: &Board) -> i32 {
+    board
+        .pieces()
+        .map(|piece| match piece.type_of_piece() {
+            InnerPiece::Knight | InnerPiece::Bishop => 1,
+            InnerPiece::Rook => 2,
+            InnerPiece::Queen => 4,
+            InnerPiece::Pawn | InnerPiece::King => 0,
+        })
+        .sum::<i32>()
+        .min(MAX_PHASE)
+}
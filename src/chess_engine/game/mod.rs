@@ -0,0 +1,321 @@
+use std::fmt::Display;
+
+use super::{
+    board::{Board, GameStatus},
+    clock::Clock,
+    errors::GameError,
+    history::History,
+    pgn::{GameResult, PgnGame},
+    pieces::{Action, Color},
+    Error,
+};
+
+// why a finished game ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    DrawAgreement,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+    TimeForfeit,
+    // the flag fell but the other side had nothing left to mate with
+    TimeoutVsInsufficientMaterial,
+}
+impl Display for Termination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Termination::Checkmate => "checkmate",
+            Termination::Stalemate => "stalemate",
+            Termination::Resignation => "resignation",
+            Termination::DrawAgreement => "draw agreed",
+            Termination::ThreefoldRepetition => "threefold repetition",
+            Termination::FivefoldRepetition => "fivefold repetition",
+            Termination::FiftyMoveRule => "fifty move rule",
+            Termination::SeventyFiveMoveRule => "seventy-five move rule",
+            Termination::InsufficientMaterial => "insufficient material",
+            Termination::TimeForfeit => "time forfeit",
+            Termination::TimeoutVsInsufficientMaterial => "timeout vs insufficient material",
+        };
+        write!(f, "{}", text)
+    }
+}
+impl Termination {
+    // the value for the pgn Termination tag, which only tells a game that ended on the board
+    // from one lost on time
+    pub fn pgn_tag(&self) -> &'static str {
+        match self {
+            Termination::TimeForfeit | Termination::TimeoutVsInsufficientMaterial => "time forfeit",
+            _ => "normal",
+        }
+    }
+}
+
+// who played and where, everything here ends up in the pgn tags
+#[derive(Debug, Clone, Default)]
+pub struct GameInfo {
+    pub white: Option<String>,
+    pub black: Option<String>,
+    pub event: Option<String>,
+    pub site: Option<String>,
+    pub date: Option<String>,
+    pub round: Option<String>,
+}
+
+// a board together with the rules around it: results, resigning and draw offers
+#[derive(Debug, Clone)]
+pub struct Game {
+    start: Board,
+    board: Board,
+    pub info: GameInfo,
+    result: GameResult,
+    termination: Option<Termination>,
+    draw_offer: Option<Color>,
+    clock: Option<Clock>,
+}
+impl Game {
+    pub fn new() -> Game {
+        Game::from_board(Board::new())
+    }
+    pub fn from_fen(fen: &str) -> Result<Game, Error> {
+        Ok(Game::from_board(Board::from_fen(fen)?))
+    }
+    // the game starts from `board` as it is now, its history is not part of the game
+    pub fn from_board(board: Board) -> Game {
+        let board = match board.history().is_empty() {
+            true => board,
+            false => Board::from_fen(&board.to_fen()).expect("a board always writes valid fen"),
+        };
+        let mut game = Game {
+            start: board.clone(),
+            board,
+            info: GameInfo::default(),
+            result: GameResult::Ongoing,
+            termination: None,
+            draw_offer: None,
+            clock: None,
+        };
+        game.update_status();
+        game
+    }
+    // replays the main line, the result written in the pgn is kept even if the moves don't show it
+    pub fn from_pgn(pgn: &PgnGame) -> Result<Game, Error> {
+        let mut game = Game::from_board(pgn.starting_board()?);
+        let board = pgn.replay()?;
+        game.board = board;
+        game.update_status();
+        if game.result == GameResult::Ongoing {
+            game.result = pgn.result;
+        }
+        let tag = |name: &str| pgn.tag(name).filter(|value| *value != "?").map(str::to_string);
+        game.info = GameInfo {
+            white: tag("White"),
+            black: tag("Black"),
+            event: tag("Event"),
+            site: tag("Site"),
+            date: tag("Date"),
+            round: tag("Round"),
+        };
+        Ok(game)
+    }
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    pub fn starting_position(&self) -> &Board {
+        &self.start
+    }
+    pub fn history(&self) -> &History {
+        self.board.history()
+    }
+    pub fn result(&self) -> GameResult {
+        self.result
+    }
+    pub fn termination(&self) -> Option<Termination> {
+        self.termination
+    }
+    pub fn is_over(&self) -> bool {
+        self.result != GameResult::Ongoing
+    }
+    pub fn turn(&self) -> Color {
+        self.board.turn()
+    }
+    pub fn draw_offer(&self) -> Option<Color> {
+        self.draw_offer
+    }
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+    // the clock of the side to move starts right away
+    pub fn set_clock(&mut self, mut clock: Clock) {
+        if !self.is_over() {
+            clock.start(self.turn());
+        }
+        self.clock = Some(clock);
+    }
+    // ends the game if the side to move ran out of time, returns whether the game is over
+    pub fn check_time(&mut self) -> bool {
+        if let Some(flagged) = self.clock.as_ref().and_then(|clock| clock.flagged()) {
+            self.time_out(flagged);
+        }
+        self.is_over()
+    }
+    fn time_out(&mut self, flagged: Color) {
+        let _ = match self.board.can_checkmate(flagged.opposite()) {
+            true => self.finish(
+                match flagged {
+                    Color::White => GameResult::BlackWins,
+                    Color::Black => GameResult::WhiteWins,
+                },
+                Termination::TimeForfeit,
+            ),
+            false => self.finish(GameResult::Draw, Termination::TimeoutVsInsufficientMaterial),
+        };
+    }
+    // the board's status, threefold and fifty move draws stay claimable until someone claims them
+    pub fn status(&self) -> GameStatus {
+        self.board.game_status()
+    }
+
+    pub fn play(&mut self, action: &Action) -> Result<GameStatus, Error> {
+        if self.check_time() {
+            Err(GameError::GameOver)?;
+        }
+        let action = self
+            .board
+            .parse_uci_move(&action.to_uci())
+            .map_err(|_| GameError::IllegalMove(action.to_uci()))?;
+        if let Some(Err(flagged)) = self.clock.as_mut().map(|clock| clock.press()) {
+            self.time_out(flagged);
+            Err(GameError::GameOver)?;
+        }
+        let mover = self.board.turn();
+        self.board.move_piece(action);
+        // moving instead of answering an offer turns it down
+        if self.draw_offer == Some(mover.opposite()) {
+            self.draw_offer = None;
+        }
+        self.update_status();
+        Ok(self.status())
+    }
+    pub fn play_san(&mut self, san: &str) -> Result<GameStatus, Error> {
+        if self.check_time() {
+            Err(GameError::GameOver)?;
+        }
+        let action = self.board.parse_san(san)?;
+        self.play(&action)
+    }
+    pub fn resign(&mut self, color: Color) -> Result<(), Error> {
+        self.finish(
+            match color {
+                Color::White => GameResult::BlackWins,
+                Color::Black => GameResult::WhiteWins,
+            },
+            Termination::Resignation,
+        )
+    }
+    pub fn offer_draw(&mut self, color: Color) -> Result<(), Error> {
+        if self.is_over() {
+            Err(GameError::GameOver)?;
+        }
+        self.draw_offer = Some(color);
+        Ok(())
+    }
+    // only the player the draw was offered to can take it
+    pub fn accept_draw(&mut self, color: Color) -> Result<(), Error> {
+        if self.draw_offer != Some(color.opposite()) {
+            Err(GameError::NoDrawOffer)?;
+        }
+        self.finish(GameResult::Draw, Termination::DrawAgreement)
+    }
+    pub fn decline_draw(&mut self, color: Color) -> Result<(), Error> {
+        if self.draw_offer != Some(color.opposite()) {
+            Err(GameError::NoDrawOffer)?;
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+    // ends the game by threefold repetition or the fifty move rule when either applies
+    pub fn claim_draw(&mut self) -> Result<(), Error> {
+        let termination = match self.status() {
+            GameStatus::ThreefoldRepetition => Termination::ThreefoldRepetition,
+            GameStatus::FiftyMoveRule => Termination::FiftyMoveRule,
+            _ => Err(GameError::NoDrawToClaim)?,
+        };
+        self.finish(GameResult::Draw, termination)
+    }
+    fn finish(&mut self, result: GameResult, termination: Termination) -> Result<(), Error> {
+        if self.is_over() {
+            Err(GameError::GameOver)?;
+        }
+        self.end(result, termination);
+        Ok(())
+    }
+    fn end(&mut self, result: GameResult, termination: Termination) {
+        self.result = result;
+        self.termination = Some(termination);
+        self.draw_offer = None;
+        if let Some(clock) = &mut self.clock {
+            clock.stop();
+        }
+    }
+    // ends the game when the board says it is over without anyone having to claim it
+    fn update_status(&mut self) {
+        let ended = match self.status() {
+            GameStatus::Checkmate {
+                winner: Color::White,
+            } => Some((GameResult::WhiteWins, Termination::Checkmate)),
+            GameStatus::Checkmate {
+                winner: Color::Black,
+            } => Some((GameResult::BlackWins, Termination::Checkmate)),
+            GameStatus::Stalemate => Some((GameResult::Draw, Termination::Stalemate)),
+            GameStatus::FivefoldRepetition => {
+                Some((GameResult::Draw, Termination::FivefoldRepetition))
+            }
+            GameStatus::SeventyFiveMoveRule => {
+                Some((GameResult::Draw, Termination::SeventyFiveMoveRule))
+            }
+            GameStatus::InsufficientMaterial => {
+                Some((GameResult::Draw, Termination::InsufficientMaterial))
+            }
+            GameStatus::Ongoing | GameStatus::ThreefoldRepetition | GameStatus::FiftyMoveRule => {
+                None
+            }
+        };
+        if let Some((result, termination)) = ended {
+            self.end(result, termination);
+        }
+    }
+
+    pub fn to_pgn(&self) -> PgnGame {
+        let mut pgn = PgnGame::from_history(&self.start, self.history(), Some(self.result));
+        let tags = [
+            ("Event", &self.info.event),
+            ("Site", &self.info.site),
+            ("Date", &self.info.date),
+            ("Round", &self.info.round),
+            ("White", &self.info.white),
+            ("Black", &self.info.black),
+        ];
+        for (name, value) in tags {
+            if let Some(value) = value {
+                pgn.set_tag(name, value);
+            }
+        }
+        if let Some(termination) = self.termination {
+            pgn.set_tag("Termination", termination.pgn_tag());
+        }
+        if let Some(clock) = &self.clock {
+            pgn.set_tag("TimeControl", &clock.time_control().to_string());
+        }
+        pgn
+    }
+}
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
@@ -1,18 +1,49 @@
-use std::ops::Deref;
-
 use super::pieces::Action;
 
 #[derive(Default,Debug,Clone)]
-pub struct History(pub Vec<Action>);
+pub struct History{
+    actions:Vec<Action>,
+    // actions taken back with undo, the last one is the first to be redone
+    undone:Vec<Action>,
+}
 impl History {
     pub fn add(&mut self,action:Action){
-        self.0.push(action);
+        self.actions.push(action);
+        self.undone.clear();
+    }
+    pub fn len(&self)->usize{
+        self.actions.len()
+    }
+    pub fn is_empty(&self)->bool{
+        self.actions.is_empty()
+    }
+    pub fn last(&self)->Option<&Action>{
+        self.actions.last()
+    }
+    pub fn can_redo(&self)->bool{
+        !self.undone.is_empty()
+    }
+    // forgets every action after the first `ply`, including anything that could be redone. the
+    // board has to be taken back first, Board::truncate_to does both
+    pub(crate) fn truncate_to(&mut self,ply:usize){
+        self.actions.truncate(ply);
+        self.undone.clear();
+    }
+    pub(crate) fn step_back(&mut self)->Option<&Action>{
+        let action=self.actions.pop()?;
+        self.undone.push(action);
+        self.undone.last()
+    }
+    pub(crate) fn step_forward(&mut self)->Option<&Action>{
+        let action=self.undone.pop()?;
+        self.actions.push(action);
+        self.actions.last()
     }
 }
 impl<'a> IntoIterator for &'a History{
     type IntoIter = std::slice::Iter<'a,Action>;
     type Item = &'a Action;
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter()
+        self.actions.iter()
     }
 }
@@ -2,4 +2,12 @@ pub mod board;
 pub mod pieces;
 pub mod history;
 pub mod errors;
+pub mod eval;
+pub mod search;
+pub mod pgn;
+pub mod game;
+pub mod clock;
+pub mod render;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub use errors::Error;
\ No newline at end of file
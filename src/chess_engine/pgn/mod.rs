@@ -0,0 +1,121 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{
+    board::Board,
+    errors::{PgnError, PgnErrorKind},
+    Error,
+};
+
+mod reader;
+mod writer;
+pub use reader::PgnReader;
+pub use writer::SEVEN_TAG_ROSTER;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    // still being played, or nobody wrote down how it ended
+    #[default]
+    Ongoing,
+}
+impl GameResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Ongoing => "*",
+        }
+    }
+}
+impl FromStr for GameResult {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1-0" => Ok(GameResult::WhiteWins),
+            "0-1" => Ok(GameResult::BlackWins),
+            "1/2-1/2" => Ok(GameResult::Draw),
+            "*" => Ok(GameResult::Ongoing),
+            _ => Err(()),
+        }
+    }
+}
+impl Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PgnMove {
+    pub san: String,
+    // numeric annotation glyphs, move suffixes like ! and ?! are turned into their $ number
+    pub nags: Vec<u8>,
+    // comments written before the move, only used at the start of a game or variation
+    pub leading_comments: Vec<String>,
+    pub comments: Vec<String>,
+    // alternatives to this move, each one starting from the position before it
+    pub variations: Vec<Vec<PgnMove>>,
+    // where the move was written, for pointing at it when it turns out to be illegal
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PgnGame {
+    // in the order they were written
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<PgnMove>,
+    pub result: GameResult,
+}
+impl PgnGame {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+    // the FEN tag if the game didn't start from the usual position
+    pub fn starting_board(&self) -> Result<Board, Error> {
+        match self.tag("FEN") {
+            Some(fen) => Board::from_fen(fen),
+            None => Ok(Board::new()),
+        }
+    }
+    // plays the main line, the board's history ends up holding every move of the game
+    pub fn replay(&self) -> Result<Board, Error> {
+        let mut board = self.starting_board()?;
+        play_line(&mut board, &self.moves)?;
+        Ok(board)
+    }
+    // checks that every move, including the ones in variations, is legal where it is played
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut board = self.starting_board()?;
+        validate_line(&mut board, &self.moves)
+    }
+}
+
+fn play_move(board: &mut Board, pgn_move: &PgnMove) -> Result<(), Error> {
+    let action = board.parse_san(&pgn_move.san).map_err(|_| PgnError {
+        line: pgn_move.line,
+        column: pgn_move.column,
+        kind: PgnErrorKind::IllegalMove(pgn_move.san.clone()),
+    })?;
+    board.move_piece(action);
+    Ok(())
+}
+fn play_line(board: &mut Board, moves: &[PgnMove]) -> Result<(), Error> {
+    moves.iter().try_for_each(|pgn_move| play_move(board, pgn_move))
+}
+fn validate_line(board: &mut Board, moves: &[PgnMove]) -> Result<(), Error> {
+    for pgn_move in moves {
+        for variation in &pgn_move.variations {
+            let mut branch = board.clone();
+            validate_line(&mut branch, variation)?;
+        }
+        play_move(board, pgn_move)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use std::{
+    io::{BufRead, Lines},
+    iter,
+};
+
+use crate::chess_engine::{
+    errors::{PgnError, PgnErrorKind},
+    Error,
+};
+
+use super::{GameResult, PgnGame, PgnMove};
+
+// reads one game at a time and only keeps the line it is working on in memory
+pub struct PgnReader<R: BufRead> {
+    lines: Lines<R>,
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    // stop after an error, the rest of the input can't be trusted to line up with games
+    failed: bool,
+}
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> PgnReader<R> {
+        PgnReader {
+            lines: reader.lines(),
+            chars: Vec::new(),
+            pos: 0,
+            line: 0,
+            failed: false,
+        }
+    }
+    fn error(&self, kind: PgnErrorKind) -> Error {
+        let (line, column) = self.location();
+        PgnError { line, column, kind }.into()
+    }
+    // the line and column of the next character
+    fn location(&self) -> (usize, usize) {
+        (self.line, self.pos + 1)
+    }
+    // every line ends in a '\n' so tokens never run across lines by accident
+    fn peek(&mut self) -> Result<Option<char>, Error> {
+        while self.pos >= self.chars.len() {
+            match self.lines.next() {
+                None => return Ok(None),
+                Some(Err(err)) => {
+                    self.line += 1;
+                    self.pos = 0;
+                    self.chars.clear();
+                    return Err(self.error(PgnErrorKind::Io(err)));
+                }
+                Some(Ok(line)) => {
+                    self.line += 1;
+                    self.pos = 0;
+                    self.chars = line.chars().chain(iter::once('\n')).collect();
+                }
+            }
+        }
+        Ok(Some(self.chars[self.pos]))
+    }
+    fn bump(&mut self) -> Result<Option<char>, Error> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Ok(c)
+    }
+    fn skip_line(&mut self) {
+        self.pos = self.chars.len();
+    }
+    // whitespace and lines starting with %, which pgn uses to hide things from readers
+    fn skip_whitespace(&mut self) -> Result<(), Error> {
+        while let Some(c) = self.peek()? {
+            match c {
+                '%' if self.pos == 0 => self.skip_line(),
+                c if c.is_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> Result<String, Error> {
+        let mut text = String::new();
+        while let Some(c) = self.peek()?.filter(|c| keep(*c)) {
+            text.push(c);
+            self.pos += 1;
+        }
+        Ok(text)
+    }
+
+    // [Name "value"]
+    fn read_tag(&mut self) -> Result<(String, String), Error> {
+        self.bump()?;
+        self.skip_whitespace()?;
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_')?;
+        if name.is_empty() {
+            return Err(self.error(PgnErrorKind::InvalidTag));
+        }
+        self.skip_whitespace()?;
+        if self.peek()? != Some('"') {
+            return Err(self.error(PgnErrorKind::InvalidTag));
+        }
+        self.bump()?;
+        let mut value = String::new();
+        loop {
+            match self.bump()? {
+                Some('"') => break,
+                Some('\\') => match self.bump()? {
+                    Some(c @ ('"' | '\\')) => value.push(c),
+                    _ => return Err(self.error(PgnErrorKind::InvalidTag)),
+                },
+                // a tag has to fit on its line
+                Some('\n') | None => return Err(self.error(PgnErrorKind::UnterminatedTag)),
+                Some(c) => value.push(c),
+            }
+        }
+        self.skip_whitespace()?;
+        match self.bump()? {
+            Some(']') => Ok((name, value)),
+            _ => Err(self.error(PgnErrorKind::UnterminatedTag)),
+        }
+    }
+    // a { } comment or a ; one running to the end of the line
+    fn read_any_comment(&mut self) -> Result<String, Error> {
+        match self.peek()? {
+            Some(';') => {
+                self.bump()?;
+                Ok(self.take_while(|c| c != '\n')?.trim().to_string())
+            }
+            _ => self.read_comment(),
+        }
+    }
+    // { can span lines }
+    fn read_comment(&mut self) -> Result<String, Error> {
+        let (line, column) = self.location();
+        self.bump()?;
+        let mut comment = String::new();
+        loop {
+            match self.bump()? {
+                Some('}') => break,
+                Some(c) => comment.push(c),
+                None => {
+                    return Err(PgnError {
+                        line,
+                        column,
+                        kind: PgnErrorKind::UnterminatedComment,
+                    }
+                    .into())
+                }
+            }
+        }
+        // line breaks inside a comment are just formatting
+        Ok(comment.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    fn read_game(&mut self) -> Result<Option<PgnGame>, Error> {
+        self.skip_whitespace()?;
+        if self.peek()?.is_none() {
+            return Ok(None);
+        }
+        let mut game = PgnGame::default();
+        while self.peek()? == Some('[') {
+            let tag = self.read_tag()?;
+            game.tags.push(tag);
+            self.skip_whitespace()?;
+        }
+        // the main line at the bottom and the variation being read on top
+        let mut lines: Vec<Vec<PgnMove>> = vec![Vec::new()];
+        let mut pending_comments = Vec::new();
+        let mut ended = false;
+        loop {
+            self.skip_whitespace()?;
+            let (line, column) = self.location();
+            let Some(c) = self.peek()? else {
+                break;
+            };
+            let in_variation = lines.len() > 1;
+            let current = lines.last_mut().expect("the main line is never popped");
+            match c {
+                // a tag without a result before it means the game just stopped
+                '[' if !in_variation => break,
+                '{' | ';' => {
+                    let comment = self.read_any_comment()?;
+                    match current.last_mut() {
+                        Some(last) => last.comments.push(comment),
+                        None => pending_comments.push(comment),
+                    }
+                }
+                '(' => {
+                    if current.is_empty() {
+                        return Err(self.error(PgnErrorKind::VariationWithoutMove));
+                    }
+                    self.bump()?;
+                    lines.push(Vec::new());
+                }
+                ')' => {
+                    if !in_variation {
+                        return Err(self.error(PgnErrorKind::UnbalancedVariation));
+                    }
+                    // the writer has nowhere to put a variation without moves, so it can't be kept
+                    if current.is_empty() {
+                        return Err(self.error(PgnErrorKind::EmptyVariation));
+                    }
+                    self.bump()?;
+                    let variation = lines.pop().expect("checked there is a variation");
+                    lines
+                        .last_mut()
+                        .and_then(|parent| parent.last_mut())
+                        .expect("variations only open after a move")
+                        .variations
+                        .push(variation);
+                }
+                '$' => {
+                    self.bump()?;
+                    let digits = self.take_while(|c| c.is_ascii_digit())?;
+                    // both point at the `$`, not past the digits
+                    let at = |kind| PgnError { line, column, kind };
+                    let nag = digits
+                        .parse::<u8>()
+                        .map_err(|_| at(PgnErrorKind::InvalidNag(digits.clone())))?;
+                    let current = lines.last_mut().expect("the main line is never popped");
+                    match current.last_mut() {
+                        Some(last) => last.nags.push(nag),
+                        None => return Err(at(PgnErrorKind::NagWithoutMove).into()),
+                    }
+                }
+                '.' => self.pos += 1,
+                c if is_symbol_char(c) => {
+                    let symbol = self.take_while(is_symbol_char)?;
+                    if let Ok(result) = symbol.parse::<GameResult>() {
+                        if in_variation {
+                            return Err(PgnError {
+                                line,
+                                column,
+                                kind: PgnErrorKind::ResultInVariation,
+                            }
+                            .into());
+                        }
+                        game.result = result;
+                        ended = true;
+                        // comments after the result still belong to this game, not the next one
+                        loop {
+                            self.skip_whitespace()?;
+                            if !matches!(self.peek()?, Some('{' | ';')) {
+                                break;
+                            }
+                            let comment = self.read_any_comment()?;
+                            match current.last_mut() {
+                                Some(last) => last.comments.push(comment),
+                                None => pending_comments.push(comment),
+                            }
+                        }
+                        break;
+                    }
+                    // move numbers, the dots after them are skipped on their own
+                    if symbol.chars().all(|c| c.is_ascii_digit()) {
+                        continue;
+                    }
+                    let current = lines.last_mut().expect("the main line is never popped");
+                    current.push(pgn_move(symbol, line, column, &mut pending_comments));
+                }
+                c => return Err(self.error(PgnErrorKind::UnexpectedChar(c))),
+            }
+        }
+        if lines.len() > 1 {
+            return Err(self.error(PgnErrorKind::UnterminatedVariation));
+        }
+        game.moves = lines.pop().expect("the main line is never popped");
+        // only comments between two games, there is no game in them
+        if game.tags.is_empty() && game.moves.is_empty() && !ended {
+            return self.read_game();
+        }
+        Ok(Some(game))
+    }
+}
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let game = self.read_game().transpose();
+        self.failed = game.as_ref().is_some_and(|game| game.is_err());
+        game
+    }
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "+#=:-/!?_*".contains(c)
+}
+// move suffixes have fixed nag numbers, !, ?, !!, ??, !? and ?!
+fn pgn_move(symbol: String, line: usize, column: usize, pending: &mut Vec<String>) -> PgnMove {
+    let san = symbol.trim_end_matches(['!', '?']);
+    let nag = match &symbol[san.len()..] {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    };
+    PgnMove {
+        san: san.to_string(),
+        nags: nag.into_iter().collect(),
+        leading_comments: std::mem::take(pending),
+        line,
+        column,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+use crate::chess_engine::{
+    board::{Board, GameStatus, STARTING_FEN},
+    history::History,
+    pieces::Color,
+};
+
+use super::{GameResult, PgnGame, PgnMove};
+
+// the tags every pgn game has to have, written first and in this order
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+const LINE_WIDTH: usize = 80;
+
+impl PgnGame {
+    // replays the history from `start` to write every move in SAN, the result is read off the
+    // final position unless one is given, a draw that only could be claimed stays `*`
+    pub fn from_history(start: &Board, history: &History, result: Option<GameResult>) -> PgnGame {
+        let mut game = PgnGame::default();
+        let mut board = start.clone();
+        for action in history {
+            game.moves.push(PgnMove {
+                san: action.to_san(&board),
+                ..Default::default()
+            });
+            board.move_piece(action.clone());
+        }
+        game.result = result.unwrap_or(match board.game_status() {
+            GameStatus::Checkmate {
+                winner: Color::White,
+            } => GameResult::WhiteWins,
+            GameStatus::Checkmate {
+                winner: Color::Black,
+            } => GameResult::BlackWins,
+            GameStatus::Stalemate
+            | GameStatus::InsufficientMaterial
+            | GameStatus::FivefoldRepetition
+            | GameStatus::SeventyFiveMoveRule => GameResult::Draw,
+            GameStatus::Ongoing | GameStatus::ThreefoldRepetition | GameStatus::FiftyMoveRule => {
+                GameResult::Ongoing
+            }
+        });
+        let fen = start.to_fen();
+        if fen != STARTING_FEN {
+            game.set_tag("SetUp", "1");
+            game.set_tag("FEN", &fen);
+        }
+        game
+    }
+    // the game that led to `board`, going back through its history to find where it started
+    pub fn from_board(board: &Board) -> PgnGame {
+        let mut start = board.clone();
+        while start.undo().is_some() {}
+        PgnGame::from_history(&start, board.history(), None)
+    }
+    // replaces the tag if it is already there
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+    fn movetext(&self) -> Vec<String> {
+        let (turn, number) = self
+            .starting_board()
+            .map_or((Color::White, 1), |board| (board.turn(), board.fullmove_number()));
+        let mut tokens = Vec::new();
+        line_tokens(&self.moves, turn, number, &mut tokens);
+        tokens.push(self.result.to_string());
+        tokens
+    }
+}
+
+fn line_tokens(moves: &[PgnMove], mut turn: Color, mut number: u32, tokens: &mut Vec<String>) {
+    // black's moves get a number when something came between them and white's move
+    let mut needs_number = true;
+    for pgn_move in moves {
+        for comment in &pgn_move.leading_comments {
+            comment_tokens(comment, tokens);
+            needs_number = true;
+        }
+        // the number stays on the same line as its move
+        tokens.push(match turn {
+            Color::White => format!("{}. {}", number, pgn_move.san),
+            Color::Black if needs_number => format!("{}... {}", number, pgn_move.san),
+            Color::Black => pgn_move.san.clone(),
+        });
+        needs_number = false;
+        for nag in &pgn_move.nags {
+            tokens.push(format!("${}", nag));
+        }
+        for comment in &pgn_move.comments {
+            comment_tokens(comment, tokens);
+            needs_number = true;
+        }
+        for variation in pgn_move.variations.iter().filter(|v| !v.is_empty()) {
+            let start = tokens.len();
+            line_tokens(variation, turn, number, tokens);
+            tokens[start].insert(0, '(');
+            tokens.last_mut().expect("the variation wrote a move").push(')');
+            needs_number = true;
+        }
+        if turn == Color::Black {
+            number += 1;
+        }
+        turn = turn.opposite();
+    }
+}
+// a word at a time so long comments can be wrapped like everything else
+fn comment_tokens(comment: &str, tokens: &mut Vec<String>) {
+    let start = tokens.len();
+    tokens.extend(comment.split_whitespace().map(str::to_string));
+    match tokens.len() == start {
+        true => tokens.push("{}".to_string()),
+        false => {
+            tokens[start].insert(0, '{');
+            tokens.last_mut().expect("the comment has a word").push('}');
+        }
+    }
+}
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Display for PgnGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for name in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.as_str(),
+                "Date" => self.tag(name).unwrap_or("????.??.??"),
+                _ => self.tag(name).unwrap_or("?"),
+            };
+            writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+        }
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+                writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+            }
+        }
+        writeln!(f)?;
+        let mut line = String::new();
+        for token in self.movetext() {
+            if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+                writeln!(f, "{}", line)?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        writeln!(f, "{}", line)
+    }
+}
+
+impl Board {
+    // the game so far as pgn, with the tags it can't know left as ?
+    pub fn to_pgn(&self) -> String {
+        PgnGame::from_board(self).to_string()
+    }
+}
@@ -1,12 +1,6 @@
-use crate::chess_engine::board::MoveOffset;
+use crate::chess_engine::board::bishop_attacks;
 
-use super::{BoardPosition, BoardWalker, MovementOptions, PieceMovement, Piece};
-static POTENTIAL_MOVES: [(i8, i8); 4] = [
-    (-1, -1), // Top
-    (1, -1),  // Left
-    (1, 1),   // Right
-    (-1, 1),  // Bottom
-];
+use super::{actions_to, BoardPosition, MovementOptions, PieceMovement, Piece};
 #[derive(Debug, Clone)]
 pub struct Bishop;
 impl PieceMovement for Bishop {
@@ -19,11 +13,6 @@ impl PieceMovement for Bishop {
     where
         Self: Sized,
     {
-        MovementOptions {
-            0: POTENTIAL_MOVES
-                .iter()
-                .map(|v| BoardWalker::new(&pos, &board, MoveOffset(v.0, v.1),piece))
-                .flatten().collect()
-        }
+        actions_to(piece, board, bishop_attacks(pos.square(), board.occupied()))
     }
 }
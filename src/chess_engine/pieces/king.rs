@@ -1,33 +1,52 @@
-use crate::chess_engine::board::MoveOffset;
+use crate::chess_engine::board::{home_rank, king_attacks, Board, CastleSide, File};
 
-use super::{BoardPosition, MovementOptions, PieceMovement, Piece, BoardWalker};
-static POTENTIAL_MOVES: [(i8, i8); 8] = [
-    (-1, -1), // Top-left
-    (-1, 0),  // Top
-    (-1, 1),  // Top-right
-    (0, -1),  // Left
-    (0, 1),   // Right
-    (1, -1),  // Bottom-left
-    (1, 0),   // Bottom
-    (1, 1),   // Bottom-right
-];
+use super::{actions_to, Action, BoardPosition, Color, InnerPiece, MovementOptions, PieceMovement, Piece};
 #[derive(Debug, Clone)]
 pub struct King;
+impl King {
+    fn can_castle(pos: &BoardPosition, board: &Board, color: &Color, side: CastleSide) -> bool {
+        let rank = home_rank(*color);
+        if !board.castling_rights().has(*color, side) || *pos != BoardPosition::new(File::E, rank) {
+            return false;
+        }
+        let rook_pos = BoardPosition::new(side.rook_file(), rank);
+        if !board[&rook_pos].is_some_and(|rook| {
+            rook.color == *color && rook.type_of_piece() == InnerPiece::Rook
+        }) {
+            return false;
+        }
+        let between: &[File] = match side {
+            CastleSide::Kingside => &[File::F, File::G],
+            CastleSide::Queenside => &[File::B, File::C, File::D],
+        };
+        // the king may not castle out of, through or into check
+        let king_path = [File::E, side.rook_destination(), side.king_destination()];
+        between
+            .iter()
+            .all(|file| !board.has_piece(&BoardPosition::new(*file, rank)))
+            && king_path
+                .iter()
+                .all(|file| !board.is_attacked(&BoardPosition::new(*file, rank), color.opposite()))
+    }
+}
 impl PieceMovement for King {
     fn get_movement_options(
         piece:&Piece,
         pos: BoardPosition,
         board: &crate::chess_engine::board::Board,
-        _: &super::Color,
+        color: &super::Color,
     ) -> MovementOptions
     where
         Self: Sized,
     {
-        MovementOptions(
-            POTENTIAL_MOVES
+        actions_to(piece, board, king_attacks(pos.square()))
             .into_iter()
-            .map(|v| BoardWalker::new(&pos, &board, MoveOffset(v.0, v.1),piece).next()
-        ).flatten().collect()
-        )
+            .chain(
+                [CastleSide::Kingside, CastleSide::Queenside]
+                    .into_iter()
+                    .filter(|side| King::can_castle(&pos, board, color, *side))
+                    .map(|side| Action::new_castle(pos, side)),
+            )
+            .collect()
     }
 }
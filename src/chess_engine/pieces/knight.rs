@@ -1,16 +1,6 @@
-use crate::chess_engine::board::MoveOffset;
+use crate::chess_engine::board::knight_attacks;
 
-use super::{BoardPosition, MovementOptions, PieceMovement, Piece, BoardWalker};
-static POTENTIAL_MOVES: [(i8, i8); 8] = [
-    (-1, 3),
-    (1, -3),
-    (1, 3),
-    (-1, -3),
-    (3, 1),
-    (3, -1),
-    (-3, 1),
-    (-3, -1),
-];
+use super::{actions_to, BoardPosition, MovementOptions, PieceMovement, Piece};
 #[derive(Debug, Clone)]
 pub struct Knight;
 impl PieceMovement for Knight {
@@ -23,11 +13,6 @@ impl PieceMovement for Knight {
     where
         Self: Sized,
     {
-        MovementOptions {
-            0: POTENTIAL_MOVES
-                .into_iter()
-                .map(|v| BoardWalker::new(&pos, &board, MoveOffset(v.0, v.1),piece).next()
-            ).flatten().collect()
-        }
+        actions_to(piece, board, knight_attacks(pos.square()))
     }
 }
@@ -1,4 +1,4 @@
-use super::{board::{Board, BoardPosition, MoveOffset, self}, errors::{Error, ActionError}};
+use super::{board::{Bitboard, Board, BoardPosition, CastleSide, CastlingRights, MoveOffset}, errors::{Error, ActionError}};
 use std::{fmt::{Debug, Display}, ops::Deref};
 mod bishop;
 mod king;
@@ -17,45 +17,156 @@ pub use rook::Rook;
 pub struct Action{
     pub piece_pos:BoardPosition,
     inner_action:InnerAction,
-    to_pos:BoardPosition,
+    pub to_pos:BoardPosition,
+    undo_info:Option<UndoInfo>,
+}
+// whatever execute throws away, filled in when the action is played so it can be taken back
+#[derive(Debug,Clone)]
+struct UndoInfo{
+    captured:Option<Piece>,
+    castling_rights:CastlingRights,
+    en_passant:Option<BoardPosition>,
+    halfmove_clock:u32,
 }
 impl Action{
     pub fn new(piece:& Piece,board:&Board,pos:BoardPosition)->Result<Self,Error>{
-        let piece_pos=piece.pos.ok_or(ActionError::PieceNotInPlay)?.clone();
+        let piece_pos=piece.pos.ok_or(ActionError::PieceNotInPlay)?;
         match (board.has_piece(&pos),board.is_piece_color(&pos,piece.color)){
             (true,true)=>{
                 Err(ActionError::SameColor.into())
             }
             (true,false)=>{
-                Ok(Action { inner_action: InnerAction::Take,piece_pos,to_pos:pos})
+                Ok(Action { inner_action: InnerAction::Take,piece_pos,to_pos:pos,undo_info:None})
             }
             (false,_)=>{
-                Ok(Action { inner_action: InnerAction::MoveTo ,piece_pos,to_pos:pos})
+                Ok(Action { inner_action: InnerAction::MoveTo ,piece_pos,to_pos:pos,undo_info:None})
             }
         }
     }
-    pub fn execute(&self, board:&mut Board){
+    pub fn new_promotion(piece:&Piece,board:&Board,pos:BoardPosition,promote_to:InnerPiece)->Result<Self,Error>{
+        let mut action=Action::new(piece,board,pos)?;
+        action.inner_action=match action.inner_action{
+            InnerAction::Take=>InnerAction::TakeAndPromote(promote_to),
+            _=>InnerAction::Promote(promote_to),
+        };
+        Ok(action)
+    }
+    // castling is written down as the king move, the rook follows along in execute
+    pub fn new_castle(king_pos:BoardPosition,side:CastleSide)->Self{
+        Action {
+            inner_action: InnerAction::Castle(side),
+            piece_pos: king_pos,
+            to_pos: BoardPosition::new(side.king_destination(), king_pos.rank),
+            undo_info: None,
+        }
+    }
+    // to_pos is the square the pawn lands on, the pawn taken sits next to where it started
+    pub fn new_en_passant(pawn_pos:BoardPosition,to_pos:BoardPosition)->Self{
+        Action { inner_action: InnerAction::EnPassant, piece_pos: pawn_pos, to_pos, undo_info: None }
+    }
+    pub fn is_en_passant(&self)->bool{
+        matches!(self.inner_action,InnerAction::EnPassant)
+    }
+    pub fn castle_side(&self)->Option<CastleSide>{
         match self.inner_action{
-            InnerAction::MoveTo=>{
-                let mut moved=board[&self.piece_pos].take().expect("Unreatcheble beacuse allready checked why hacking");
-                moved.pos=Some(self.piece_pos);
-                board[&self.to_pos]=Some(moved)
-            }
+            InnerAction::Castle(side)=>Some(side),
+            _=>None,
+        }
+    }
+    pub fn promotion(&self)->Option<InnerPiece>{
+        match self.inner_action{
+            InnerAction::Promote(piece)|InnerAction::TakeAndPromote(piece)=>Some(piece),
+            _=>None,
+        }
+    }
+    pub fn is_capture(&self)->bool{
+        matches!(self.inner_action,InnerAction::Take|InnerAction::EnPassant|InnerAction::TakeAndPromote(_))
+    }
+    pub fn execute(&mut self, board:&mut Board){
+        let mut undo_info=UndoInfo{
+            captured:None,
+            castling_rights:board.castling_rights(),
+            en_passant:board.en_passant(),
+            halfmove_clock:board.halfmove_clock(),
+        };
+        let mut moved=board.remove_piece(&self.piece_pos).expect("Unreatcheble beacuse allready checked why hacking");
+        let pawn_move=moved.type_of_pice==InnerPiece::Pawn;
+        match self.inner_action{
+            InnerAction::MoveTo=>{}
             InnerAction::Take=>{
-                let mut piece=board[&self.to_pos].take().expect("Unreatcheble beacuse allready checked why hacking");
-                piece.pos=None;
-                let mut moved=board[&self.piece_pos].take().expect("Unreatcheble beacuse allready checked why hacking");
-                moved.pos=Some(self.piece_pos);
-                board[&self.to_pos]=Some(moved)
+                undo_info.captured=Some(board.remove_piece(&self.to_pos).expect("Unreatcheble beacuse allready checked why hacking"));
+            }
+            InnerAction::Promote(piece)=>{
+                moved.type_of_pice=piece;
+            }
+            InnerAction::TakeAndPromote(piece)=>{
+                undo_info.captured=Some(board.remove_piece(&self.to_pos).expect("Unreatcheble beacuse allready checked why hacking"));
+                moved.type_of_pice=piece;
+            }
+            InnerAction::EnPassant=>{
+                let taken_pos=BoardPosition::new(self.to_pos.file,self.piece_pos.rank);
+                undo_info.captured=Some(board.remove_piece(&taken_pos).expect("the en passant square is only set behind a pawn"));
+            }
+            InnerAction::Castle(side)=>{
+                let rook_pos=BoardPosition::new(side.rook_file(),self.piece_pos.rank);
+                let rook=board.remove_piece(&rook_pos).expect("castling rights say the rook is still there");
+                board.put_piece(BoardPosition::new(side.rook_destination(),self.piece_pos.rank),rook);
             }
         }
+        board.put_piece(self.to_pos,moved);
+        board.revoke_castling_rights(&self.piece_pos,&self.to_pos);
+        let double_step=pawn_move
+            && (i8::from(self.to_pos.rank)-i8::from(self.piece_pos.rank)).abs()==2;
+        board.set_en_passant(match double_step{
+            true=>(self.piece_pos+MoveOffset(moved.color.forward(),0)).ok(),
+            false=>None,
+        });
+        board.finish_turn(self.is_capture() || pawn_move);
+        self.undo_info=Some(undo_info);
+    }
+    // puts the board back the way it was before execute, so only call it on the last action played
+    pub fn undo(&self, board:&mut Board){
+        let undo_info=self.undo_info.as_ref().expect("an action has to be executed before it can be undone");
+        let mut moved=board.remove_piece(&self.to_pos).expect("the moved piece is still where it went");
+        match self.inner_action{
+            InnerAction::Promote(_)|InnerAction::TakeAndPromote(_)=>{
+                moved.type_of_pice=InnerPiece::Pawn;
+            }
+            InnerAction::Castle(side)=>{
+                let rook_to=BoardPosition::new(side.rook_destination(),self.piece_pos.rank);
+                let rook=board.remove_piece(&rook_to).expect("the rook is still next to the king");
+                board.put_piece(BoardPosition::new(side.rook_file(),self.piece_pos.rank),rook);
+            }
+            _=>{}
+        }
+        board.put_piece(self.piece_pos,moved);
+        if let Some(captured)=undo_info.captured{
+            let captured_pos=captured.pos.expect("captured pieces keep the square they were taken on");
+            board.put_piece(captured_pos,captured);
+        }
+        board.rewind_turn(undo_info.castling_rights,undo_info.en_passant,undo_info.halfmove_clock);
+    }
+}
+// coordinate notation like e2e4 or e7e8q, castling is written as the king move
+impl Display for Action{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f,"{}{}",self.piece_pos,self.to_pos)?;
+        if let Some(piece)=self.promotion(){
+            write!(f,"{}",piece.fen_char())?;
+        }
+        Ok(())
     }
 }
 #[derive(Debug,Clone)]
 enum InnerAction {
     Take,
     MoveTo,
+    EnPassant,
+    Castle(CastleSide),
+    Promote(InnerPiece),
+    TakeAndPromote(InnerPiece),
 }
+#[derive(Debug,Default)]
 pub struct MovementOptions(Vec<Action>);
 impl Deref for MovementOptions{
     type Target = Vec<Action>;
@@ -63,6 +174,18 @@ impl Deref for MovementOptions{
         &self.0
     }
 }
+impl IntoIterator for MovementOptions{
+    type IntoIter = std::vec::IntoIter<Action>;
+    type Item = Action;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl FromIterator<Action> for MovementOptions{
+    fn from_iter<T: IntoIterator<Item = Action>>(iter: T) -> Self {
+        MovementOptions(iter.into_iter().collect())
+    }
+}
 trait PieceMovement {
     fn get_movement_options(
         piece:&Piece,
@@ -76,6 +199,24 @@ pub enum Color {
     Black,
     White,
 }
+impl Color {
+    pub fn opposite(&self) -> Color {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+    // the direction pawns of this color walk along the ranks
+    pub fn forward(&self) -> i8 {
+        match self {
+            Color::Black => -1,
+            Color::White => 1,
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub struct Piece {
     pub color: Color,
@@ -83,7 +224,7 @@ pub struct Piece {
     type_of_pice: InnerPiece,
 }
 #[derive(Debug, Clone, PartialEq, Copy)]
-enum InnerPiece {
+pub enum InnerPiece {
     Bishop,
     King,
     Knight,
@@ -91,8 +232,46 @@ enum InnerPiece {
     Queen,
     Rook,
 }
+impl InnerPiece {
+    pub const ALL: [InnerPiece; 6] = [
+        InnerPiece::Bishop,
+        InnerPiece::King,
+        InnerPiece::Knight,
+        InnerPiece::Pawn,
+        InnerPiece::Queen,
+        InnerPiece::Rook,
+    ];
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+    // the pieces a pawn may turn into, best first
+    pub const PROMOTIONS: [InnerPiece; 4] = [
+        InnerPiece::Queen,
+        InnerPiece::Rook,
+        InnerPiece::Bishop,
+        InnerPiece::Knight,
+    ];
+    // the lowercase FEN letter, which is also what coordinate notation uses for promotions
+    pub fn fen_char(&self) -> char {
+        match self {
+            InnerPiece::Bishop => 'b',
+            InnerPiece::King => 'k',
+            InnerPiece::Knight => 'n',
+            InnerPiece::Pawn => 'p',
+            InnerPiece::Queen => 'q',
+            InnerPiece::Rook => 'r',
+        }
+    }
+}
 
 impl Piece {
+    pub fn new(color: Color, type_of_pice: InnerPiece) -> Piece {
+        Piece {
+            color,
+            pos: None,
+            type_of_pice,
+        }
+    }
     pub fn new_pawn(color: Color) -> Piece {
         Piece {
             color,
@@ -137,6 +316,33 @@ impl Piece {
     }
 }
 impl Piece {
+    pub fn type_of_piece(&self) -> InnerPiece {
+        self.type_of_pice
+    }
+    // the letter used for this piece in FEN, uppercase for white
+    pub fn fen_char(&self) -> char {
+        let c = self.type_of_pice.fen_char();
+        match self.color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c,
+        }
+    }
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let color = match c.is_ascii_uppercase() {
+            true => Color::White,
+            false => Color::Black,
+        };
+        let type_of_pice = match c.to_ascii_lowercase() {
+            'b' => InnerPiece::Bishop,
+            'k' => InnerPiece::King,
+            'n' => InnerPiece::Knight,
+            'p' => InnerPiece::Pawn,
+            'q' => InnerPiece::Queen,
+            'r' => InnerPiece::Rook,
+            _ => return None,
+        };
+        Some(Piece::new(color, type_of_pice))
+    }
     pub fn get_movement_options(&self, board: &Board) -> Option<MovementOptions>
     where
         Self: Sized,
@@ -152,29 +358,14 @@ impl Piece {
     }
 }
 
-pub(super) struct BoardWalker<'a> {
-    piece:&'a Piece,
-    pos: BoardPosition,
-    board: &'a Board,
-    changer: MoveOffset,
-}
-impl<'a> BoardWalker<'a> {
-    fn new(pos: &BoardPosition, board: &'a Board, changer: MoveOffset,piece:&'a Piece) -> BoardWalker<'a> {
-        BoardWalker {
-            piece,
-            pos:pos.clone(),
-            board,
-            changer,
-        }
-    }
-}
-impl<'a> Iterator for BoardWalker<'a> {
-    type Item = Action;
-    fn next(&mut self) -> Option<Self::Item> {
-        let new_pos = (self.pos.clone() + self.changer).ok()?;
-        self.pos = new_pos;
-        Action::new(self.piece, self.board, self.pos).ok()
-    }
+// one action for every square in `targets`, taking whatever stands there
+fn actions_to(piece: &Piece, board: &Board, targets: Bitboard) -> MovementOptions {
+    let targets = targets & !board.occupancy(piece.color);
+    MovementOptions(
+        targets
+            .filter_map(|square| Action::new(piece, board, BoardPosition::from_square(square)).ok())
+            .collect(),
+    )
 }
 impl Display for Piece {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
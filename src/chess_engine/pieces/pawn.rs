@@ -1,6 +1,6 @@
-use crate::chess_engine::board::{MoveOffset, Rank};
+use crate::chess_engine::board::{home_rank, pawn_attacks, MoveOffset, Rank};
 
-use super::{Action, BoardPosition, Color, MovementOptions, PieceMovement, Piece};
+use super::{Action, BoardPosition, Color, InnerPiece, MovementOptions, PieceMovement, Piece};
 
 #[derive(Debug, Clone)]
 pub struct Pawn;
@@ -15,39 +15,46 @@ impl PieceMovement for Pawn {
         Self: Sized,
     {
         let mut potential_moves = Vec::new();
+        let forward = color.forward();
+        let start_rank = match color {
+            Color::White => Rank::Two,
+            Color::Black => Rank::Seven,
+        };
+        // reaching the other sides home rank always promotes, one action per piece choice
+        let promotion_rank = home_rank(color.opposite());
+        let push_move = |potential_moves: &mut Vec<Option<Action>>, target: BoardPosition| {
+            if target.rank == promotion_rank {
+                for promote_to in InnerPiece::PROMOTIONS {
+                    potential_moves.push(Action::new_promotion(piece, board, target, promote_to).ok());
+                }
+            } else {
+                potential_moves.push(Action::new(piece, board, target).ok());
+            }
+        };
 
-        if pos.rank == Rank::Two && *color == Color::White {
-            let temp_pos = (pos.clone() + MoveOffset(2, 0)).ok();
-            potential_moves.push(temp_pos.and_then(|v| match board.has_piece(&v) {
-                true => None,
-                false => Action::new(piece, board, v).ok(),
-            }))
-        } else if pos.rank == Rank::Seven && *color == Color::Black {
-            let temp_pos = (pos.clone() + MoveOffset(-2, 0)).ok();
-            potential_moves.push(temp_pos.and_then(|v| match board.has_piece(&v) {
-                true => None,
-                false => Action::new(piece, board, v).ok(),
-            }))
+        let single_step = (pos + MoveOffset(forward, 0))
+            .ok()
+            .filter(|v| !board.has_piece(v));
+        if let Some(target) = single_step {
+            push_move(&mut potential_moves, target);
+            // the double step is only possible when the square in between is free as well
+            if pos.rank == start_rank {
+                if let Some(target) = (pos + MoveOffset(2 * forward, 0))
+                    .ok()
+                    .filter(|v| !board.has_piece(v))
+                {
+                    push_move(&mut potential_moves, target);
+                }
+            }
         }
 
-        let temp_pos = (pos.clone() + MoveOffset(1, 1)).ok();
-        potential_moves.push(temp_pos.and_then(|v| match board.has_piece(&v) {
-            true => Action::new(piece, board, v).ok(),
-            false => None,
-        }));
-
-        let temp_pos = (pos.clone() + MoveOffset(1, 1)).ok();
-        potential_moves.push(temp_pos.and_then(|v| match board.has_piece(&v) {
-            true => Action::new(piece, board, v).ok(),
-            false => None,
-        }));
-        let temp_pos = (pos.clone() + MoveOffset(1, 0)).ok();
-        potential_moves.push(temp_pos.and_then(|v| match board.has_piece(&v) {
-            true => Action::new(piece, board, v).ok(),
-            false => None,
-        }));
-        MovementOptions {
-            0: potential_moves.into_iter().flatten().collect(),
+        let attacks = pawn_attacks(*color, pos.square());
+        for square in attacks & board.occupancy(color.opposite()) {
+            push_move(&mut potential_moves, BoardPosition::from_square(square));
+        }
+        if let Some(target) = board.en_passant().filter(|v| attacks.contains(v.square())) {
+            potential_moves.push(Some(Action::new_en_passant(pos, target)));
         }
+        MovementOptions(potential_moves.into_iter().flatten().collect())
     }
 }
@@ -1,12 +1,6 @@
-use crate::chess_engine::board::MoveOffset;
+use crate::chess_engine::board::rook_attacks;
 
-use super::{ BoardPosition, BoardWalker, MovementOptions, PieceMovement, Piece};
-static POTENTIAL_MOVES: [(i8, i8); 4] = [
-    (-1, 0), // Top
-    (0, -1), // Left
-    (0, 1),  // Right
-    (1, 0),  // Bottom
-];
+use super::{actions_to, BoardPosition, MovementOptions, PieceMovement, Piece};
 #[derive(Debug, Clone)]
 pub struct Rook;
 impl PieceMovement for Rook {
@@ -19,11 +13,6 @@ impl PieceMovement for Rook {
     where
         Self: Sized,
     {
-        MovementOptions {
-            0: POTENTIAL_MOVES
-                .iter()
-                .map(|v| BoardWalker::new(&pos, &board, MoveOffset(v.0, v.1),piece))
-                .flatten().collect()
-        }
+        actions_to(piece, board, rook_attacks(pos.square(), board.occupied()))
     }
 }
@@ -0,0 +1,30 @@
+mod svg;
+mod text;
+pub use svg::{Arrow, SvgRenderer, Theme};
+pub use text::{PieceStyle, TextRenderer};
+
+use super::{
+    board::{Bitboard, Board},
+    pieces::InnerPiece,
+};
+
+// the squares of the last move played on `board`
+pub fn last_move_squares(board: &Board) -> Bitboard {
+    let mut squares = Bitboard::EMPTY;
+    if let Some(action) = board.history().last() {
+        squares.set(action.piece_pos.square());
+        squares.set(action.to_pos.square());
+    }
+    squares
+}
+// the square of the king of the side to move if it is in check
+pub fn check_square(board: &Board) -> Bitboard {
+    match board.is_in_check(board.turn()) {
+        true => board.pieces_of(board.turn(), InnerPiece::King),
+        false => Bitboard::EMPTY,
+    }
+}
+// a1 is dark
+pub fn is_light_square(square: u8) -> bool {
+    (square / 8 + square % 8) % 2 == 1
+}
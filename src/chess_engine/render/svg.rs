@@ -0,0 +1,282 @@
+use std::fmt::Write;
+
+use crate::chess_engine::{
+    board::{Bitboard, Board, BoardPosition, File, Rank},
+    pieces::{Action, Color, InnerPiece},
+};
+
+use super::{check_square, is_light_square, last_move_squares};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub light: String,
+    pub dark: String,
+    // drawn over the squares, so it should be see through
+    pub highlight: String,
+    pub check: String,
+    pub arrow: String,
+}
+impl Theme {
+    fn new(light: &str, dark: &str, highlight: &str, check: &str, arrow: &str) -> Theme {
+        Theme {
+            light: light.to_string(),
+            dark: dark.to_string(),
+            highlight: highlight.to_string(),
+            check: check.to_string(),
+            arrow: arrow.to_string(),
+        }
+    }
+    pub fn brown() -> Theme {
+        Theme::new("#f0d9b5", "#b58863", "#cdd26a99", "#e83a3acc", "#15781bcc")
+    }
+    pub fn blue() -> Theme {
+        Theme::new("#dee3e6", "#8ca2ad", "#9bc70080", "#e83a3acc", "#003088cc")
+    }
+    pub fn green() -> Theme {
+        Theme::new("#eeeed2", "#769656", "#f6f66999", "#e83a3acc", "#e68f00cc")
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::brown()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arrow {
+    pub from: BoardPosition,
+    pub to: BoardPosition,
+    // the theme's arrow colour when None
+    pub color: Option<String>,
+}
+impl Arrow {
+    pub fn new(from: BoardPosition, to: BoardPosition) -> Arrow {
+        Arrow {
+            from,
+            to,
+            color: None,
+        }
+    }
+}
+impl From<&Action> for Arrow {
+    fn from(action: &Action) -> Self {
+        Arrow::new(action.piece_pos, action.to_pos)
+    }
+}
+
+// draws a board as a standalone svg, the pieces are text glyphs so nothing has to be loaded
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    // the width and height in pixels
+    pub size: u32,
+    pub theme: Theme,
+    // the rank and file names in the corner squares
+    pub coordinates: bool,
+    // with black at the bottom
+    pub flipped: bool,
+    pub last_move: bool,
+    pub check: bool,
+    pub highlights: Bitboard,
+    pub arrows: Vec<Arrow>,
+}
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            size: 400,
+            theme: Theme::default(),
+            coordinates: true,
+            flipped: false,
+            last_move: true,
+            check: true,
+            highlights: Bitboard::EMPTY,
+            arrows: Vec::new(),
+        }
+    }
+}
+impl SvgRenderer {
+    pub fn render(&self, board: &Board) -> String {
+        let mut highlights = self.highlights;
+        if self.last_move {
+            highlights |= last_move_squares(board);
+        }
+        let square_size = self.square_size();
+        let mut svg = String::new();
+        // writing to a string can't fail
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            self.size
+        );
+        for square in 0..64 {
+            let (x, y) = self.corner(square);
+            let fill = match is_light_square(square) {
+                true => &self.theme.light,
+                false => &self.theme.dark,
+            };
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{2:.1}" height="{2:.1}" fill="{3}"/>"#,
+                x, y, square_size, fill
+            );
+            if highlights.contains(square) {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{:.1}" width="{2:.1}" height="{2:.1}" fill="{3}"/>"#,
+                    x, y, square_size, self.theme.highlight
+                );
+            }
+        }
+        if self.check {
+            if let Some(square) = check_square(board).first() {
+                let (x, y) = self.center(square);
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}"/>"#,
+                    x,
+                    y,
+                    square_size * 0.45,
+                    self.theme.check
+                );
+            }
+        }
+        if self.coordinates {
+            self.write_coordinates(&mut svg);
+        }
+        for square in 0..64 {
+            let Some(piece) = board[&BoardPosition::from_square(square)] else {
+                continue;
+            };
+            let (x, y) = self.center(square);
+            let (fill, stroke) = match piece.color {
+                Color::White => ("#ffffff", "#000000"),
+                Color::Black => ("#000000", "#000000"),
+            };
+            // the solid glyph for both sides, with the variation selector that keeps it from
+            // turning into an emoji
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" text-anchor="middle" dominant-baseline="central" font-family="DejaVu Sans, Segoe UI Symbol, serif" fill="{}" stroke="{}" stroke-width="{:.1}">{}&#xFE0E;</text>"#,
+                x,
+                y,
+                square_size * 0.8,
+                fill,
+                stroke,
+                square_size * 0.02,
+                glyph(piece.type_of_piece())
+            );
+        }
+        for arrow in &self.arrows {
+            self.write_arrow(&mut svg, arrow);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+    fn square_size(&self) -> f64 {
+        self.size as f64 / 8.0
+    }
+    // the column and row the square is drawn at, counted from the top left
+    fn cell(&self, square: u8) -> (u8, u8) {
+        let (file, rank) = (square % 8, square / 8);
+        match self.flipped {
+            true => (7 - file, rank),
+            false => (file, 7 - rank),
+        }
+    }
+    fn corner(&self, square: u8) -> (f64, f64) {
+        let (column, row) = self.cell(square);
+        (
+            column as f64 * self.square_size(),
+            row as f64 * self.square_size(),
+        )
+    }
+    fn center(&self, square: u8) -> (f64, f64) {
+        let (x, y) = self.corner(square);
+        (x + self.square_size() / 2.0, y + self.square_size() / 2.0)
+    }
+    // files along the bottom row and ranks down the left column, in the other square colour
+    fn write_coordinates(&self, svg: &mut String) {
+        let square_size = self.square_size();
+        let font_size = square_size * 0.2;
+        let (bottom_rank, left_file) = match self.flipped {
+            true => (Rank::Eight, File::H),
+            false => (Rank::One, File::A),
+        };
+        let contrast = |square: u8| match is_light_square(square) {
+            true => &self.theme.dark,
+            false => &self.theme.light,
+        };
+        for file in File::ALL {
+            let square = BoardPosition::new(file, bottom_rank).square();
+            let (x, y) = self.corner(square);
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" text-anchor="end" font-family="sans-serif" font-weight="bold" fill="{}">{}</text>"#,
+                x + square_size - font_size * 0.3,
+                y + square_size - font_size * 0.3,
+                font_size,
+                contrast(square),
+                char::from(file)
+            );
+        }
+        for rank in Rank::ALL {
+            let square = BoardPosition::new(left_file, rank).square();
+            let (x, y) = self.corner(square);
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="{:.1}" font-family="sans-serif" font-weight="bold" fill="{}">{}</text>"#,
+                x + font_size * 0.3,
+                y + font_size * 1.1,
+                font_size,
+                contrast(square),
+                i8::from(rank)
+            );
+        }
+    }
+    // a shaft from the middle of one square and a head that ends in the middle of the other
+    fn write_arrow(&self, svg: &mut String, arrow: &Arrow) {
+        let square_size = self.square_size();
+        let color = arrow.color.as_ref().unwrap_or(&self.theme.arrow);
+        let (x1, y1) = self.center(arrow.from.square());
+        let (x2, y2) = self.center(arrow.to.square());
+        let length = (x2 - x1).hypot(y2 - y1);
+        if length == 0.0 {
+            return;
+        }
+        let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+        let head = square_size * 0.45;
+        let (bx, by) = (x2 - dx * head, y2 - dy * head);
+        let (px, py) = (-dy * head / 2.0, dx * head / 2.0);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{:.1}" stroke-linecap="round"/>"#,
+            x1,
+            y1,
+            bx,
+            by,
+            color,
+            square_size * 0.18
+        );
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="{}"/>"#,
+            x2,
+            y2,
+            bx + px,
+            by + py,
+            bx - px,
+            by - py,
+            color
+        );
+    }
+}
+
+fn glyph(piece: InnerPiece) -> char {
+    match piece {
+        InnerPiece::King => '♚',
+        InnerPiece::Queen => '♛',
+        InnerPiece::Rook => '♜',
+        InnerPiece::Bishop => '♝',
+        InnerPiece::Knight => '♞',
+        InnerPiece::Pawn => '♟',
+    }
+}
@@ -0,0 +1,153 @@
+use crate::chess_engine::{
+    board::{Bitboard, Board, BoardPosition, File, Rank},
+    pieces::{Color, InnerPiece, Piece},
+};
+
+use super::{check_square, is_light_square, last_move_squares};
+
+const RESET: &str = "\x1b[0m";
+const LIGHT: &str = "\x1b[48;5;180m";
+const DARK: &str = "\x1b[48;5;137m";
+const LIGHT_HIGHLIGHT: &str = "\x1b[48;5;186m";
+const DARK_HIGHLIGHT: &str = "\x1b[48;5;143m";
+const CHECK: &str = "\x1b[48;5;167m";
+const WHITE_PIECE: &str = "\x1b[1;38;5;231m";
+const BLACK_PIECE: &str = "\x1b[1;38;5;16m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PieceStyle {
+    // the FEN letters, uppercase for white
+    #[default]
+    Ascii,
+    Unicode,
+}
+
+// draws a board as text for terminals and logs
+#[derive(Debug, Clone)]
+pub struct TextRenderer {
+    pub style: PieceStyle,
+    // shades the squares and colours the pieces with ansi escapes
+    pub ansi: bool,
+    pub coordinates: bool,
+    // with black at the bottom
+    pub flipped: bool,
+    pub last_move: bool,
+    pub check: bool,
+    // extra squares to mark
+    pub highlights: Bitboard,
+}
+impl Default for TextRenderer {
+    fn default() -> Self {
+        TextRenderer {
+            style: PieceStyle::Ascii,
+            ansi: false,
+            coordinates: true,
+            flipped: false,
+            last_move: true,
+            check: true,
+            highlights: Bitboard::EMPTY,
+        }
+    }
+}
+impl TextRenderer {
+    pub fn render(&self, board: &Board) -> String {
+        let mut highlights = self.highlights;
+        if self.last_move {
+            highlights |= last_move_squares(board);
+        }
+        let check = match self.check {
+            true => check_square(board),
+            false => Bitboard::EMPTY,
+        };
+        let (ranks, files) = self.orientation();
+        let mut text = String::new();
+        for rank in &ranks {
+            let mut line = String::new();
+            if self.coordinates {
+                line.push_str(&format!("{} ", i8::from(rank)));
+            }
+            for file in &files {
+                let pos = BoardPosition::new(*file, *rank);
+                let square = pos.square();
+                let piece = board[&pos];
+                match self.ansi {
+                    true => {
+                        let background = match (
+                            check.contains(square),
+                            highlights.contains(square),
+                            is_light_square(square),
+                        ) {
+                            (true, _, _) => CHECK,
+                            (false, true, true) => LIGHT_HIGHLIGHT,
+                            (false, true, false) => DARK_HIGHLIGHT,
+                            (false, false, true) => LIGHT,
+                            (false, false, false) => DARK,
+                        };
+                        let foreground = match piece.map(|piece| piece.color) {
+                            Some(Color::Black) => BLACK_PIECE,
+                            _ => WHITE_PIECE,
+                        };
+                        let symbol = piece.map_or(' ', |piece| self.symbol(piece));
+                        line.push_str(&format!("{}{} {} ", background, foreground, symbol));
+                    }
+                    false => {
+                        let symbol = piece.map_or('.', |piece| self.symbol(piece));
+                        match highlights.contains(square) || check.contains(square) {
+                            true => line.push_str(&format!("[{}]", symbol)),
+                            false => line.push_str(&format!(" {} ", symbol)),
+                        }
+                    }
+                }
+            }
+            if self.ansi {
+                line.push_str(RESET);
+            }
+            // no trailing spaces to trip over when the board is pasted somewhere
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        if self.coordinates {
+            let mut line = String::from("  ");
+            for file in &files {
+                line.push_str(&format!(" {} ", char::from(*file)));
+            }
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+    // the ranks from the top down and the files from left to right
+    fn orientation(&self) -> (Vec<Rank>, Vec<File>) {
+        let mut ranks: Vec<Rank> = Rank::ALL.iter().rev().copied().collect();
+        let mut files = File::ALL.to_vec();
+        if self.flipped {
+            ranks.reverse();
+            files.reverse();
+        }
+        (ranks, files)
+    }
+    fn symbol(&self, piece: Piece) -> char {
+        match self.style {
+            PieceStyle::Ascii => piece.fen_char(),
+            // with colours on the solid glyphs read best for both sides
+            PieceStyle::Unicode => match (piece.color, self.ansi) {
+                (Color::White, false) => match piece.type_of_piece() {
+                    InnerPiece::King => '♔',
+                    InnerPiece::Queen => '♕',
+                    InnerPiece::Rook => '♖',
+                    InnerPiece::Bishop => '♗',
+                    InnerPiece::Knight => '♘',
+                    InnerPiece::Pawn => '♙',
+                },
+                _ => match piece.type_of_piece() {
+                    InnerPiece::King => '♚',
+                    InnerPiece::Queen => '♛',
+                    InnerPiece::Rook => '♜',
+                    InnerPiece::Bishop => '♝',
+                    InnerPiece::Knight => '♞',
+                    InnerPiece::Pawn => '♟',
+                },
+            },
+        }
+    }
+}
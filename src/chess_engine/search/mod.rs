@@ -0,0 +1,327 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use super::{
+    board::Board,
+    eval::{piece_value, Evaluator},
+    pieces::Action,
+};
+
+mod transposition;
+pub use transposition::{Bound, Entry, PackedMove, TranspositionTable, DEFAULT_HASH_MB};
+
+pub const MATE_SCORE: i32 = 100_000;
+const INFINITY: i32 = MATE_SCORE + 1;
+// scores past this are mates, the distance to mate is MATE_SCORE minus the score
+const MATE_THRESHOLD: i32 = MATE_SCORE - 1_000;
+const MAX_DEPTH: u32 = 64;
+
+#[derive(Debug, Default, Clone)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub best_move: Option<Action>,
+    // centipawns from the point of view of the side to move
+    pub score: i32,
+    pub pv: Vec<Action>,
+    pub depth: u32,
+    pub nodes: u64,
+    pub elapsed: Duration,
+    // permille of the transposition table in use
+    pub hashfull: u32,
+}
+impl SearchResult {
+    // moves until mate, negative when the side to move is getting mated
+    pub fn mate_in(&self) -> Option<i32> {
+        match self.score {
+            score if score > MATE_THRESHOLD => Some((MATE_SCORE - score + 1) / 2),
+            score if score < -MATE_THRESHOLD => Some(-(MATE_SCORE + score) / 2),
+            _ => None,
+        }
+    }
+}
+
+pub struct Search {
+    limits: SearchLimits,
+    evaluator: Evaluator,
+    table: TranspositionTable,
+    stop: Arc<AtomicBool>,
+    start: Instant,
+    nodes: u64,
+    aborted: bool,
+}
+impl Search {
+    pub fn new(limits: SearchLimits) -> Search {
+        Search::with_stop(limits, Arc::new(AtomicBool::new(false)))
+    }
+    // the flag lets another thread end the search early
+    pub fn with_stop(limits: SearchLimits, stop: Arc<AtomicBool>) -> Search {
+        Search::with_table(limits, stop, TranspositionTable::new(DEFAULT_HASH_MB))
+    }
+    // hand in a table from an earlier search to keep what it learned
+    pub fn with_table(
+        limits: SearchLimits,
+        stop: Arc<AtomicBool>,
+        table: TranspositionTable,
+    ) -> Search {
+        Search {
+            limits,
+            evaluator: Evaluator::default(),
+            table,
+            stop,
+            start: Instant::now(),
+            nodes: 0,
+            aborted: false,
+        }
+    }
+    pub fn set_evaluator(&mut self, evaluator: Evaluator) {
+        self.evaluator = evaluator;
+    }
+    pub fn table(&self) -> &TranspositionTable {
+        &self.table
+    }
+    pub fn into_table(self) -> TranspositionTable {
+        self.table
+    }
+    // iterative deepening, `on_iteration` gets the result of every depth that finished
+    pub fn run(
+        &mut self,
+        board: &mut Board,
+        mut on_iteration: impl FnMut(&SearchResult),
+    ) -> SearchResult {
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.aborted = false;
+        self.table.new_search();
+        let mut result = SearchResult {
+            best_move: board.legal_moves().into_iter().next(),
+            score: 0,
+            pv: Vec::new(),
+            depth: 0,
+            nodes: 0,
+            elapsed: Duration::ZERO,
+            hashfull: 0,
+        };
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+        for depth in 1..=max_depth {
+            let mut pv = Vec::new();
+            let score = self.negamax(board, depth, 0, -INFINITY, INFINITY, &result.pv, &mut pv);
+            // a search cut short only looked at part of the tree, so its answer can't be trusted
+            if self.aborted {
+                break;
+            }
+            result = SearchResult {
+                best_move: pv.first().cloned().or(result.best_move),
+                score,
+                pv,
+                depth,
+                nodes: self.nodes,
+                elapsed: self.start.elapsed(),
+                hashfull: self.table.hashfull(),
+            };
+            on_iteration(&result);
+            if result.mate_in().is_some() || result.best_move.is_none() {
+                break;
+            }
+        }
+        result.nodes = self.nodes;
+        result.elapsed = self.start.elapsed();
+        result.hashfull = self.table.hashfull();
+        result
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        previous_pv: &[Action],
+        pv: &mut Vec<Action>,
+    ) -> i32 {
+        if depth == 0 {
+            return self.quiescence(board, alpha, beta);
+        }
+        self.visit();
+        if self.aborted {
+            return 0;
+        }
+        // a repeated position is scored as a draw right away, the side that is better will avoid it
+        if ply > 0 && is_draw(board) {
+            return 0;
+        }
+        let key = board.hash();
+        let entry = self.table.probe(key).copied();
+        // the root always searches so there is a move to play
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth >= depth) {
+            let score = score_from_table(entry.score, ply);
+            let usable = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+            if usable {
+                return score;
+            }
+        }
+        let mut moves: Vec<Action> = board.legal_moves().into_iter().collect();
+        if moves.is_empty() {
+            return match board.is_in_check(board.turn()) {
+                true => -MATE_SCORE + ply as i32,
+                false => 0,
+            };
+        }
+        order_moves(
+            board,
+            &mut moves,
+            previous_pv.first(),
+            entry.and_then(|entry| entry.best_move),
+        );
+        let original_alpha = alpha;
+        let mut best_move = None;
+        for mut action in moves {
+            let following_pv = match previous_pv.first() {
+                Some(first) if same_move(first, &action) => &previous_pv[1..],
+                _ => &[],
+            };
+            let mut child_pv = Vec::new();
+            action.execute(board);
+            let score = -self.negamax(
+                board,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                following_pv,
+                &mut child_pv,
+            );
+            action.undo(board);
+            if self.aborted {
+                return 0;
+            }
+            if score > alpha {
+                alpha = score;
+                best_move = Some(PackedMove::from(&action));
+                pv.clear();
+                pv.push(action);
+                pv.append(&mut child_pv);
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        let bound = match alpha {
+            alpha if alpha >= beta => Bound::Lower,
+            alpha if alpha > original_alpha => Bound::Exact,
+            _ => Bound::Upper,
+        };
+        self.table
+            .store(key, depth, score_to_table(alpha, ply), bound, best_move);
+        alpha
+    }
+    // only captures are searched past the horizon so we don't stop in the middle of a trade
+    fn quiescence(&mut self, board: &mut Board, mut alpha: i32, beta: i32) -> i32 {
+        self.visit();
+        if self.aborted {
+            return 0;
+        }
+        let stand_pat = self.evaluator.evaluate(board);
+        if stand_pat >= beta {
+            return beta;
+        }
+        alpha = alpha.max(stand_pat);
+        let mut captures: Vec<Action> = board
+            .legal_moves()
+            .into_iter()
+            .filter(|action| action.is_capture() || action.promotion().is_some())
+            .collect();
+        order_moves(board, &mut captures, None, None);
+        for mut action in captures {
+            action.execute(board);
+            let score = -self.quiescence(board, -beta, -alpha);
+            action.undo(board);
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+    fn visit(&mut self) {
+        self.nodes += 1;
+        if self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes) {
+            self.aborted = true;
+            return;
+        }
+        // checking the clock is not free, so only do it every so often
+        if !self.nodes.is_multiple_of(1024) {
+            return;
+        }
+        let out_of_time = self.limits.time.is_some_and(|time| self.start.elapsed() >= time);
+        if out_of_time || self.stop.load(Ordering::Relaxed) {
+            self.aborted = true;
+        }
+    }
+}
+
+// draws that don't depend on the moves left, mate takes priority over the fifty move rule but
+// checking for it here would cost a move generation in every node
+fn is_draw(board: &Board) -> bool {
+    board.repetitions() >= 2 || board.halfmove_clock() >= 100 || board.is_insufficient_material()
+}
+// mate scores are stored as the distance from the stored position instead of from the root
+fn score_to_table(score: i32, ply: u32) -> i32 {
+    match score {
+        score if score > MATE_THRESHOLD => score + ply as i32,
+        score if score < -MATE_THRESHOLD => score - ply as i32,
+        score => score,
+    }
+}
+fn score_from_table(score: i32, ply: u32) -> i32 {
+    match score {
+        score if score > MATE_THRESHOLD => score - ply as i32,
+        score if score < -MATE_THRESHOLD => score + ply as i32,
+        score => score,
+    }
+}
+fn same_move(a: &Action, b: &Action) -> bool {
+    a.piece_pos == b.piece_pos && a.to_pos == b.to_pos && a.promotion() == b.promotion()
+}
+// the previous best move first, then the table's move, then captures with the most valuable
+// victim and cheapest attacker
+fn order_moves(
+    board: &Board,
+    moves: &mut [Action],
+    best: Option<&Action>,
+    table_move: Option<PackedMove>,
+) {
+    moves.sort_by_cached_key(|action| {
+        if best.is_some_and(|best| same_move(best, action)) {
+            return i32::MIN;
+        }
+        if table_move.is_some_and(|table_move| table_move.matches(action)) {
+            return i32::MIN + 1;
+        }
+        let victim = board[&action.to_pos].map_or(0, |piece| piece_value(piece.type_of_piece()));
+        let attacker = board[&action.piece_pos].map_or(0, |piece| piece_value(piece.type_of_piece()));
+        let promotion = action.promotion().map_or(0, piece_value);
+        match action.is_capture() || promotion > 0 {
+            true => -(victim * 10 - attacker + promotion),
+            false => 0,
+        }
+    });
+}
@@ -0,0 +1,138 @@
+use std::mem::size_of;
+
+use crate::chess_engine::pieces::{Action, InnerPiece};
+
+pub const DEFAULT_HASH_MB: usize = 16;
+
+// what the stored score says about the real score of the position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Exact,
+    // the search failed high, the real score is at least this
+    Lower,
+    // the search failed low, the real score is at most this
+    Upper,
+}
+
+// just enough of a move to find it again among the legal moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PackedMove {
+    from: u8,
+    to: u8,
+    promotion: Option<InnerPiece>,
+}
+impl PackedMove {
+    pub fn matches(&self, action: &Action) -> bool {
+        self.from == action.piece_pos.square()
+            && self.to == action.to_pos.square()
+            && self.promotion == action.promotion()
+    }
+}
+impl From<&Action> for PackedMove {
+    fn from(action: &Action) -> Self {
+        PackedMove {
+            from: action.piece_pos.square(),
+            to: action.to_pos.square(),
+            promotion: action.promotion(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub key: u64,
+    pub depth: u32,
+    pub score: i32,
+    pub bound: Bound,
+    pub best_move: Option<PackedMove>,
+    generation: u8,
+}
+
+// a fixed number of slots indexed by the position hash, every slot holds at most one position
+#[derive(Debug, Default)]
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+    generation: u8,
+}
+impl TranspositionTable {
+    pub fn new(megabytes: usize) -> TranspositionTable {
+        let mut table = TranspositionTable::default();
+        table.resize(megabytes);
+        table
+    }
+    // throws away everything stored so far
+    pub fn resize(&mut self, megabytes: usize) {
+        let slots = megabytes * 1024 * 1024 / size_of::<Option<Entry>>();
+        self.entries = vec![None; slots];
+        self.generation = 0;
+    }
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+        self.generation = 0;
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    // entries from earlier searches are the first to be replaced
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+    fn index(&self, key: u64) -> Option<usize> {
+        match self.entries.len() {
+            0 => None,
+            len => Some((key % len as u64) as usize),
+        }
+    }
+    pub fn probe(&self, key: u64) -> Option<&Entry> {
+        self.entries[self.index(key)?]
+            .as_ref()
+            .filter(|entry| entry.key == key)
+    }
+    // deeper results are kept over shallower ones unless they are left over from an older search
+    pub fn store(
+        &mut self,
+        key: u64,
+        depth: u32,
+        score: i32,
+        bound: Bound,
+        best_move: Option<PackedMove>,
+    ) {
+        let Some(index) = self.index(key) else {
+            return;
+        };
+        let generation = self.generation;
+        let slot = &mut self.entries[index];
+        let replace = match slot {
+            None => true,
+            Some(old) => old.key == key || old.generation != generation || depth >= old.depth,
+        };
+        if !replace {
+            return;
+        }
+        // a bound without a move shouldn't forget the move we already knew for this position
+        let best_move = best_move.or(slot.filter(|old| old.key == key).and_then(|old| old.best_move));
+        *slot = Some(Entry {
+            key,
+            depth,
+            score,
+            bound,
+            best_move,
+            generation,
+        });
+    }
+    // how full the table is in permille, sampled from the first thousand slots like uci expects
+    pub fn hashfull(&self) -> u32 {
+        let sample = &self.entries[..self.entries.len().min(1000)];
+        if sample.is_empty() {
+            return 0;
+        }
+        let used = sample
+            .iter()
+            .filter(|entry| entry.is_some_and(|entry| entry.generation == self.generation))
+            .count();
+        (used * 1000 / sample.len()) as u32
+    }
+}
@@ -0,0 +1,134 @@
+// json friendly forms for the public types: colours as words, squares as names, pieces as their
+// fen letter, moves in coordinate notation and boards as fen plus the moves that led there.
+// colours, squares, pieces and boards go both ways. a move or a history on its own only
+// serializes, neither can be made sense of without the position it was played from, so moves
+// are read back with ActionSeed and histories only as part of their board
+use serde::{
+    de::{DeserializeSeed, Error as _},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{
+    board::{Board, BoardPosition},
+    history::History,
+    pieces::{Action, Color, Piece},
+};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Color::White => "white",
+            Color::Black => "black",
+        })
+    }
+}
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "white" => Ok(Color::White),
+            "black" => Ok(Color::Black),
+            other => Err(D::Error::custom(format!(
+                "expected white or black, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl Serialize for BoardPosition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for BoardPosition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|err| D::Error::custom(format!("invalid square {}: {}", text, err)))
+    }
+}
+
+// only the kind and colour, the square a piece stands on belongs to the board
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.fen_char())
+    }
+}
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let letter = char::deserialize(deserializer)?;
+        Piece::from_fen_char(letter)
+            .ok_or_else(|| D::Error::custom(format!("invalid piece {}", letter)))
+    }
+}
+
+impl Serialize for Action {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_uci())
+    }
+}
+// coordinate notation doesn't say whether a move captures or castles, so an action can only be
+// read back against the board it is played on
+pub struct ActionSeed<'a>(pub &'a Board);
+impl<'de> DeserializeSeed<'de> for ActionSeed<'_> {
+    type Value = Action;
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Action, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        self.0
+            .parse_uci_move(&text)
+            .map_err(|err| D::Error::custom(format!("invalid move {}: {}", text, err)))
+    }
+}
+
+// the moves played, there is no Deserialize, the board replays them to build its history
+impl Serialize for History {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BoardJson {
+    fen: String,
+    // where the moves start from, the same as fen when there are none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    moves: Vec<String>,
+}
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut start = self.clone();
+        while start.undo().is_some() {}
+        let moves: Vec<String> = self.history().into_iter().map(Action::to_uci).collect();
+        BoardJson {
+            fen: self.to_fen(),
+            start: (!moves.is_empty()).then(|| start.to_fen()),
+            moves,
+        }
+        .serialize(serializer)
+    }
+}
+// replays the moves from the start, they have to end up at the fen
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = BoardJson::deserialize(deserializer)?;
+        let start = json.start.as_deref().unwrap_or(&json.fen);
+        let mut board = Board::from_fen(start)
+            .map_err(|err| D::Error::custom(format!("{} ({})", err, start)))?;
+        for text in &json.moves {
+            let action = board
+                .parse_uci_move(text)
+                .map_err(|err| D::Error::custom(format!("invalid move {}: {}", text, err)))?;
+            board.move_piece(action);
+        }
+        if board.to_fen() != json.fen {
+            return Err(D::Error::custom(format!(
+                "the moves lead to {} instead of {}",
+                board.to_fen(),
+                json.fen
+            )));
+        }
+        Ok(board)
+    }
+}
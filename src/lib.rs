@@ -0,0 +1,5 @@
+pub mod chess_engine;
+pub mod output;
+pub mod repl;
+pub mod uci;
+pub mod xboard;
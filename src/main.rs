@@ -1,9 +1,83 @@
-#![feature(allocator_api)]
-use chess_engine::board::Board;
+use std::{
+    env,
+    io::{self, BufRead},
+};
 
-mod chess_engine;
+use rusttesting::{
+    chess_engine::board::Board,
+    repl::{EngineSide, Repl},
+    uci::Uci,
+    xboard::Xboard,
+};
 
 fn main() {
-    let board=Board::new();
-    println!("{}",board)
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("perft") => perft(&args[1..]),
+        Some("play") => play(&args[1..]),
+        Some("board") => {
+            let board=Board::new();
+            println!("{}",board)
+        }
+        _ => engine(),
+    }
+}
+
+// the first thing the gui sends tells us which protocol it speaks, anything but xboard is uci
+fn engine() {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines().map_while(Result::ok);
+    let Some(first) = lines.by_ref().find(|line| !line.trim().is_empty()) else {
+        return;
+    };
+    match first.trim() {
+        "xboard" => Xboard::new().run(lines),
+        first => {
+            let mut uci = Uci::new();
+            if uci.handle(first) {
+                uci.run(lines);
+            }
+        }
+    }
+}
+
+// play [white|black|both|off], the sides the engine plays, black unless told otherwise
+fn play(args: &[String]) {
+    let engine = match args.first() {
+        Some(side) => match EngineSide::parse(side) {
+            Some(engine) => engine,
+            None => {
+                eprintln!("usage: play [white|black|both|off]");
+                return;
+            }
+        },
+        None => EngineSide::Black,
+    };
+    let stdin = io::stdin();
+    Repl::new(engine).run(stdin.lock().lines().map_while(Result::ok));
+}
+
+// perft <depth> [fen], prints the node count for every root move followed by the total
+fn perft(args: &[String]) {
+    let Some(depth) = args.first().and_then(|depth| depth.parse::<u32>().ok()) else {
+        eprintln!("usage: perft <depth> [fen]");
+        return;
+    };
+    let board = match args.len() {
+        1 => Ok(Board::new()),
+        _ => Board::from_fen(&args[1..].join(" ")),
+    };
+    let mut board = match board {
+        Ok(board) => board,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        }
+    };
+    let divide = board.perft_divide(depth);
+    for (action, nodes) in &divide {
+        println!("{}: {}", action, nodes);
+    }
+    println!();
+    println!("Nodes searched: {}", divide.iter().map(|(_, nodes)| nodes).sum::<u64>());
 }
@@ -0,0 +1,34 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+// where a front end writes its replies, cloned into the search threads so the lines they print
+// don't get mixed up with the main thread's
+#[derive(Clone)]
+pub struct Output(Arc<Mutex<Box<dyn Write + Send>>>);
+impl Output {
+    pub fn new(writer: impl Write + Send + 'static) -> Output {
+        Output(Arc::new(Mutex::new(Box::new(writer))))
+    }
+    pub fn stdout() -> Output {
+        Output::new(io::stdout())
+    }
+    pub fn line(&self, text: impl Display) {
+        self.write(format_args!("{}\n", text));
+    }
+    // flushed straight away, the gui on the other end is waiting for it
+    pub fn write(&self, text: impl Display) {
+        // a poisoned lock only means another thread panicked halfway through writing
+        let mut writer = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        // there is nobody left to tell when the other end has gone away
+        let _ = write!(writer, "{}", text);
+        let _ = writer.flush();
+    }
+}
+impl Default for Output {
+    fn default() -> Self {
+        Output::stdout()
+    }
+}
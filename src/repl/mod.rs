@@ -0,0 +1,322 @@
+use std::time::Duration;
+
+use crate::chess_engine::{
+    board::{Board, GameStatus},
+    eval::{phase, Evaluator},
+    pieces::{Action, Color},
+    render::{PieceStyle, TextRenderer},
+    search::{Search, SearchLimits, SearchResult, TranspositionTable, DEFAULT_HASH_MB},
+    errors::{SanError, UciMoveError},
+    Error,
+};
+use crate::output::Output;
+
+const HELP: &str = "\
+moves are written in SAN (Nf3, exd5, O-O, e8=Q) or coordinates (g1f3, e7e8q)
+  new [fen]            start over, from the fen if one is given
+  undo                 take back your last move
+  flip                 turn the board around
+  unicode on|off       draw the pieces as chess symbols
+  color on|off         shade the squares and colour the pieces
+  fen                  print the position as fen
+  pgn                  print the game so far as pgn
+  hint                 ask the engine for a move
+  eval                 show how the engine sees the position
+  go                   let the engine play the side to move
+  engine white|black|both|off
+                       choose the sides the engine plays
+  time <seconds>       how long the engine thinks per move
+  help                 show this
+  quit                 leave";
+
+// which sides the engine plays, a human plays the rest
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EngineSide {
+    White,
+    Black,
+    Both,
+    Off,
+}
+impl EngineSide {
+    pub fn parse(text: &str) -> Option<EngineSide> {
+        match text {
+            "white" => Some(EngineSide::White),
+            "black" => Some(EngineSide::Black),
+            "both" => Some(EngineSide::Both),
+            "off" | "none" => Some(EngineSide::Off),
+            _ => None,
+        }
+    }
+    fn plays(&self, color: Color) -> bool {
+        match self {
+            EngineSide::White => color == Color::White,
+            EngineSide::Black => color == Color::Black,
+            EngineSide::Both => true,
+            EngineSide::Off => false,
+        }
+    }
+}
+
+pub struct Repl {
+    board: Board,
+    output: Output,
+    engine: EngineSide,
+    renderer: TextRenderer,
+    think_time: Duration,
+    evaluator: Evaluator,
+    table: TranspositionTable,
+}
+impl Repl {
+    pub fn new(engine: EngineSide) -> Repl {
+        Repl::with_output(engine, Output::stdout())
+    }
+    pub fn with_output(engine: EngineSide, output: Output) -> Repl {
+        Repl {
+            board: Board::new(),
+            output,
+            engine,
+            renderer: TextRenderer {
+                // look at the board from the human's side
+                flipped: engine == EngineSide::White,
+                ..Default::default()
+            },
+            think_time: Duration::from_secs(2),
+            evaluator: Evaluator::default(),
+            table: TranspositionTable::new(DEFAULT_HASH_MB),
+        }
+    }
+    pub fn run(&mut self, mut lines: impl Iterator<Item = String>) {
+        self.output.line("type help for the commands");
+        self.show();
+        self.engine_moves();
+        loop {
+            self.output.write("> ");
+            let Some(line) = lines.next() else {
+                break;
+            };
+            if !self.handle(line.trim()) {
+                break;
+            }
+        }
+    }
+    // returns false once the user wants to leave
+    pub fn handle(&mut self, line: &str) -> bool {
+        let mut tokens = line.split_whitespace();
+        let Some(command) = tokens.next() else {
+            return true;
+        };
+        match command {
+            "quit" | "exit" => return false,
+            "help" => self.output.line(HELP),
+            "new" => {
+                let fen: Vec<&str> = tokens.collect();
+                let board = match fen.is_empty() {
+                    true => Ok(Board::new()),
+                    false => Board::from_fen(&fen.join(" ")),
+                };
+                match board {
+                    Ok(board) => {
+                        self.board = board;
+                        self.table.clear();
+                        self.show();
+                        self.engine_moves();
+                    }
+                    Err(err) => self.output.line(err),
+                }
+            }
+            "undo" => self.undo(),
+            "flip" => {
+                self.renderer.flipped = !self.renderer.flipped;
+                self.show();
+            }
+            "unicode" => match tokens.next().and_then(parse_switch) {
+                Some(on) => {
+                    self.renderer.style = match on {
+                        true => PieceStyle::Unicode,
+                        false => PieceStyle::Ascii,
+                    };
+                    self.show();
+                }
+                None => self.output.line("usage: unicode on|off"),
+            },
+            "color" | "colour" => match tokens.next().and_then(parse_switch) {
+                Some(on) => {
+                    self.renderer.ansi = on;
+                    self.show();
+                }
+                None => self.output.line("usage: color on|off"),
+            },
+            "fen" => self.output.line(self.board.to_fen()),
+            "pgn" => self.output.write(self.board.to_pgn()),
+            "hint" => match self.search(self.think_time).best_move {
+                Some(action) => self
+                    .output
+                    .line(format_args!("hint: {}", action.to_san(&self.board))),
+                None => self.output.line("there are no moves to play"),
+            },
+            "eval" => self.eval(),
+            "go" => {
+                self.engine_move();
+                self.engine_moves();
+            }
+            "engine" => match tokens.next().and_then(EngineSide::parse) {
+                Some(engine) => {
+                    self.engine = engine;
+                    self.engine_moves();
+                }
+                None => self.output.line("usage: engine white|black|both|off"),
+            },
+            "time" => match tokens
+                .next()
+                .and_then(|seconds| seconds.parse::<f64>().ok())
+            {
+                Some(seconds) if seconds > 0.0 => {
+                    self.think_time = Duration::from_secs_f64(seconds)
+                }
+                _ => self.output.line("usage: time <seconds>"),
+            },
+            text => match parse_move(&self.board, text) {
+                Ok(action) => {
+                    if self.play(action) {
+                        self.engine_moves();
+                    }
+                }
+                Err(Error::UciMove(UciMoveError::InvalidMove(_))) => {
+                    self.output
+                        .line(format_args!("not a move or command: {}, try help", text))
+                }
+                Err(err) => self.output.line(err),
+            },
+        }
+        true
+    }
+
+    // plays the move and shows the result, returns whether the game goes on
+    fn play(&mut self, action: Action) -> bool {
+        let san = action.to_san(&self.board);
+        let number = self.board.fullmove_number();
+        let dots = match self.board.turn() {
+            Color::White => ".",
+            Color::Black => "...",
+        };
+        self.board.move_piece(action);
+        self.show();
+        self.output.line(format_args!("{}{} {}", number, dots, san));
+        let status = self.board.game_status();
+        if let Some(text) = describe(status) {
+            self.output.line(text);
+        }
+        !status.is_over()
+    }
+    // lets the engine answer until it is a human's turn again
+    fn engine_moves(&mut self) {
+        while self.engine.plays(self.board.turn()) && !self.board.game_status().is_over() {
+            if !self.engine_move() {
+                break;
+            }
+        }
+    }
+    fn engine_move(&mut self) -> bool {
+        match self.search(self.think_time).best_move {
+            Some(action) => self.play(action),
+            None => false,
+        }
+    }
+    fn search(&mut self, time: Duration) -> SearchResult {
+        let limits = SearchLimits {
+            time: Some(time),
+            ..Default::default()
+        };
+        let table = std::mem::take(&mut self.table);
+        let mut search = Search::with_table(limits, Default::default(), table);
+        let mut board = self.board.clone();
+        let result = search.run(&mut board, |_| {});
+        self.table = search.into_table();
+        result
+    }
+    // takes back moves until it is a human's turn again
+    fn undo(&mut self) {
+        if self.board.undo().is_none() {
+            self.output.line("nothing to undo");
+            return;
+        }
+        while self.engine.plays(self.board.turn()) && self.engine != EngineSide::Both {
+            if self.board.undo().is_none() {
+                break;
+            }
+        }
+        self.show();
+    }
+    fn eval(&self) {
+        for (name, score) in self.evaluator.breakdown(&self.board) {
+            self.output.line(format_args!(
+                "{:<20} mg {:>6} eg {:>6}",
+                name, score.mg, score.eg
+            ));
+        }
+        self.output.line(format_args!("{:<20} {}", "phase", phase(&self.board)));
+        let score = match self.board.turn() {
+            Color::White => self.evaluator.evaluate(&self.board),
+            Color::Black => -self.evaluator.evaluate(&self.board),
+        };
+        self.output.line(format_args!("{:<20} {} (from white's side)", "total", score));
+    }
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    fn show(&self) {
+        self.output.write(self.renderer.render(&self.board));
+    }
+}
+
+fn describe(status: GameStatus) -> Option<&'static str> {
+    let text = match status {
+        GameStatus::Ongoing => return None,
+        GameStatus::Checkmate {
+            winner: Color::White,
+        } => "1-0, white mates",
+        GameStatus::Checkmate {
+            winner: Color::Black,
+        } => "0-1, black mates",
+        GameStatus::Stalemate => "1/2-1/2, stalemate",
+        GameStatus::ThreefoldRepetition | GameStatus::FivefoldRepetition => {
+            "1/2-1/2, draw by repetition"
+        }
+        GameStatus::FiftyMoveRule | GameStatus::SeventyFiveMoveRule => {
+            "1/2-1/2, draw by the fifty move rule"
+        }
+        GameStatus::InsufficientMaterial => "1/2-1/2, insufficient material",
+    };
+    Some(text)
+}
+fn parse_switch(text: &str) -> Option<bool> {
+    match text {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+// coordinates when it has their shape, g1f3 would read as a pawn move in san. anything else is
+// san, with coordinates as the fallback only so a typo gets the right message
+fn parse_move(board: &Board, text: &str) -> Result<Action, Error> {
+    if is_coordinates(text) {
+        return board.parse_uci_move(text);
+    }
+    board.parse_san(text).or_else(|err| match err {
+        Error::San(SanError::InvalidSan(_)) => board.parse_uci_move(text),
+        err => Err(err),
+    })
+}
+// two squares and maybe a promotion letter
+fn is_coordinates(text: &str) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    let square =
+        |file: char, rank: char| ('a'..='h').contains(&file) && ('1'..='8').contains(&rank);
+    match chars.as_slice() {
+        [a, b, c, d] => square(*a, *b) && square(*c, *d),
+        [a, b, c, d, promotion] => {
+            square(*a, *b) && square(*c, *d) && "qrbn".contains(*promotion)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::chess_engine::{
+    board::Board,
+    pieces::Color,
+    search::{Search, SearchLimits, SearchResult, TranspositionTable, DEFAULT_HASH_MB},
+    Error,
+};
+use crate::output::Output;
+
+pub const ENGINE_NAME: &str = "rust_chess_engine";
+const ENGINE_AUTHOR: &str = "barbariand";
+pub const MAX_HASH_MB: usize = 4096;
+
+// everything `go` can be told, all times are in milliseconds
+#[derive(Debug, Default, Clone)]
+pub struct GoLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<u64>,
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u64>,
+    pub infinite: bool,
+}
+impl GoLimits {
+    pub fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>) -> GoLimits {
+        let mut limits = GoLimits::default();
+        while let Some(token) = tokens.next() {
+            let mut value = || tokens.next().and_then(|v| v.parse::<u64>().ok());
+            match token {
+                "depth" => limits.depth = value().map(|v| v as u32),
+                "nodes" => limits.nodes = value(),
+                "movetime" => limits.movetime = value(),
+                "wtime" => limits.wtime = value(),
+                "btime" => limits.btime = value(),
+                "winc" => limits.winc = value(),
+                "binc" => limits.binc = value(),
+                "movestogo" => limits.movestogo = value(),
+                "infinite" => limits.infinite = true,
+                _ => {}
+            }
+        }
+        limits
+    }
+    // how long we allow ourselves to think, None means until told to stop
+    pub fn time_budget(&self, color: Color) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+        if let Some(movetime) = self.movetime {
+            return Some(Duration::from_millis(movetime));
+        }
+        let (time, inc) = match color {
+            Color::White => (self.wtime?, self.winc.unwrap_or(0)),
+            Color::Black => (self.btime?, self.binc.unwrap_or(0)),
+        };
+        let moves_left = self.movestogo.unwrap_or(30).max(1);
+        // keep a little back so we never lose on time because of overhead
+        let budget = (time / moves_left + inc * 3 / 4).min(time.saturating_sub(50));
+        Some(Duration::from_millis(budget.max(1)))
+    }
+    pub fn search_limits(&self, color: Color) -> SearchLimits {
+        SearchLimits {
+            depth: self.depth,
+            nodes: self.nodes,
+            time: self.time_budget(color),
+        }
+    }
+}
+
+pub struct Uci {
+    board: Board,
+    output: Output,
+    stop: Arc<AtomicBool>,
+    // dropped to wake an infinite search that finished before it was told to stop
+    stopped: Option<Sender<()>>,
+    // lent to the search thread while it runs and handed back when it is joined
+    table: TranspositionTable,
+    search: Option<JoinHandle<TranspositionTable>>,
+}
+impl Uci {
+    pub fn new() -> Uci {
+        Uci::with_output(Output::stdout())
+    }
+    pub fn with_output(output: Output) -> Uci {
+        Uci {
+            board: Board::new(),
+            output,
+            stop: Arc::new(AtomicBool::new(false)),
+            stopped: None,
+            table: TranspositionTable::new(DEFAULT_HASH_MB),
+            search: None,
+        }
+    }
+    pub fn run(&mut self, lines: impl Iterator<Item = String>) {
+        for line in lines {
+            if !self.handle(&line) {
+                break;
+            }
+        }
+        self.stop_search();
+    }
+    // returns false once the engine should shut down
+    pub fn handle(&mut self, line: &str) -> bool {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("uci") => {
+                self.output.line(format_args!("id name {}", ENGINE_NAME));
+                self.output.line(format_args!("id author {}", ENGINE_AUTHOR));
+                self.output.line(format_args!(
+                    "option name Hash type spin default {} min 1 max {}",
+                    DEFAULT_HASH_MB, MAX_HASH_MB
+                ));
+                self.output.line("uciok");
+            }
+            Some("isready") => self.output.line("readyok"),
+            Some("ucinewgame") => {
+                self.stop_search();
+                self.board = Board::new();
+                self.table.clear();
+            }
+            Some("setoption") => {
+                self.stop_search();
+                self.set_option(tokens);
+            }
+            Some("position") => {
+                self.stop_search();
+                match parse_position(tokens) {
+                    Ok(board) => self.board = board,
+                    Err(err) => self
+                        .output
+                        .line(format_args!("info string invalid position: {}", err)),
+                }
+            }
+            Some("go") => {
+                self.stop_search();
+                self.go(GoLimits::parse(tokens));
+            }
+            Some("stop") => self.stop_search(),
+            Some("quit") => return false,
+            _ => {}
+        }
+        true
+    }
+    fn go(&mut self, limits: GoLimits) {
+        self.stop = Arc::new(AtomicBool::new(false));
+        let (stopped, wait_for_stop) = mpsc::channel::<()>();
+        self.stopped = Some(stopped);
+        let stop = self.stop.clone();
+        let output = self.output.clone();
+        let mut board = self.board.clone();
+        let search_limits = limits.search_limits(board.turn());
+        let table = mem::take(&mut self.table);
+        self.search = Some(thread::spawn(move || {
+            let mut search = Search::with_table(search_limits, stop, table);
+            let result = search.run(&mut board, |result| print_info(&output, result));
+            // with infinite the gui expects no bestmove before it says stop, nothing is ever
+            // sent so this returns once the sender is dropped
+            if limits.infinite {
+                let _ = wait_for_stop.recv();
+            }
+            output.line(format_args!(
+                "bestmove {}",
+                result.best_move.map_or("0000".to_string(), |a| a.to_uci())
+            ));
+            search.into_table()
+        }));
+    }
+    // setoption name <id> [value <x>]
+    fn set_option<'a>(&mut self, mut tokens: impl Iterator<Item = &'a str>) {
+        if tokens.next() != Some("name") {
+            return;
+        }
+        let name: Vec<&str> = tokens.by_ref().take_while(|t| *t != "value").collect();
+        let value: Vec<&str> = tokens.collect();
+        match name.join(" ").to_lowercase().as_str() {
+            "hash" => match value.join(" ").parse::<usize>() {
+                Ok(megabytes) => self.table.resize(megabytes.clamp(1, MAX_HASH_MB)),
+                Err(_) => self.output.line(format_args!(
+                    "info string invalid hash size: {}",
+                    value.join(" ")
+                )),
+            },
+            name => self
+                .output
+                .line(format_args!("info string unknown option: {}", name)),
+        }
+    }
+    fn stop_search(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.stopped = None;
+        if let Some(search) = self.search.take() {
+            // a search thread that panicked takes its table with it
+            self.table = search
+                .join()
+                .unwrap_or_else(|_| TranspositionTable::new(DEFAULT_HASH_MB));
+        }
+    }
+}
+
+impl Default for Uci {
+    fn default() -> Self {
+        Uci::new()
+    }
+}
+
+fn print_info(output: &Output, result: &SearchResult) {
+    let score = match result.mate_in() {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", result.score),
+    };
+    let millis = result.elapsed.as_millis().max(1);
+    let pv: Vec<String> = result.pv.iter().map(|a| a.to_uci()).collect();
+    output.line(format_args!(
+        "info depth {} score {} nodes {} nps {} hashfull {} time {} pv {}",
+        result.depth,
+        score,
+        result.nodes,
+        result.nodes as u128 * 1000 / millis,
+        result.hashfull,
+        result.elapsed.as_millis(),
+        pv.join(" ")
+    ));
+}
+
+// position [startpos | fen <fen>] [moves <move>...]
+fn parse_position<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Board, String> {
+    let board = match tokens.next() {
+        Some("startpos") => match tokens.next() {
+            Some("moves") | None => Board::new(),
+            Some(token) => return Err(format!("expected moves, got {}", token)),
+        },
+        Some("fen") => {
+            let fen: Vec<&str> = tokens.by_ref().take_while(|t| *t != "moves").collect();
+            Board::from_fen(&fen.join(" ")).map_err(|err| err.to_string())?
+        }
+        _ => return Err("expected startpos or fen".to_string()),
+    };
+    apply_moves(board, tokens).map_err(|err| err.to_string())
+}
+fn apply_moves<'a>(mut board: Board, moves: impl Iterator<Item = &'a str>) -> Result<Board, Error> {
+    for text in moves {
+        let action = board.parse_uci_move(text)?;
+        board.move_piece(action);
+    }
+    Ok(board)
+}
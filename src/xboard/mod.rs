@@ -0,0 +1,380 @@
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::chess_engine::{
+    board::{Board, GameStatus},
+    pieces::{Action, Color},
+    search::{Search, SearchLimits, SearchResult, TranspositionTable, DEFAULT_HASH_MB},
+};
+
+use crate::{
+    output::Output,
+    uci::{GoLimits, ENGINE_NAME, MAX_HASH_MB},
+};
+
+// level <moves per session> <base> <increment>, base is minutes or minutes:seconds
+#[derive(Debug, Default, Clone)]
+struct Level {
+    moves: u32,
+    base: Duration,
+    increment: Duration,
+}
+impl Level {
+    fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Option<Level> {
+        let moves = tokens.next()?.parse().ok()?;
+        let base = tokens.next()?;
+        let base = match base.split_once(':') {
+            Some((minutes, seconds)) => {
+                minutes.parse::<u64>().ok()? * 60 + seconds.parse::<u64>().ok()?
+            }
+            None => base.parse::<u64>().ok()? * 60,
+        };
+        let increment = tokens.next()?.parse::<f64>().ok()?;
+        Some(Level {
+            moves,
+            base: Duration::from_secs(base),
+            increment: Duration::from_secs_f64(increment.max(0.0)),
+        })
+    }
+}
+
+struct SearchHandle {
+    // set when the search should end without sending its move
+    cancel: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    // the move, sent before it is printed so the board has it before the gui can answer
+    moves: Receiver<Action>,
+    thread: JoinHandle<TranspositionTable>,
+}
+
+pub struct Xboard {
+    board: Board,
+    output: Output,
+    // in force mode the engine only keeps track of the moves it is told
+    force: bool,
+    engine_color: Color,
+    post: bool,
+    level: Level,
+    move_time: Option<Duration>,
+    depth: Option<u32>,
+    // centiseconds on our and the opponent's clock, as the last time and otim said
+    time: Option<u64>,
+    opponent_time: Option<u64>,
+    table: TranspositionTable,
+    search: Option<SearchHandle>,
+}
+impl Xboard {
+    pub fn new() -> Xboard {
+        Xboard::with_output(Output::stdout())
+    }
+    pub fn with_output(output: Output) -> Xboard {
+        Xboard {
+            board: Board::new(),
+            output,
+            force: false,
+            engine_color: Color::Black,
+            post: false,
+            level: Level::default(),
+            move_time: None,
+            depth: None,
+            time: None,
+            opponent_time: None,
+            table: TranspositionTable::new(DEFAULT_HASH_MB),
+            search: None,
+        }
+    }
+    pub fn run(&mut self, lines: impl Iterator<Item = String>) {
+        for line in lines {
+            if !self.handle(&line) {
+                break;
+            }
+        }
+        self.cancel_search();
+    }
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    // returns false once the engine should shut down
+    pub fn handle(&mut self, line: &str) -> bool {
+        // whatever comes next was sent knowing about a move that already went out
+        self.collect_move();
+        let mut tokens = line.split_whitespace();
+        let Some(command) = tokens.next() else {
+            return true;
+        };
+        match command {
+            "protover" => self.output.line(format_args!(
+                "feature ping=1 setboard=1 usermove=1 time=1 draw=0 sigint=0 sigterm=0 reuse=1 \
+                 analyze=0 colors=0 memory=1 myname=\"{}\" done=1",
+                ENGINE_NAME
+            )),
+            "new" => {
+                self.cancel_search();
+                self.board = Board::new();
+                self.force = false;
+                self.engine_color = Color::Black;
+                self.depth = None;
+                self.table.clear();
+            }
+            "force" => {
+                self.cancel_search();
+                self.force = true;
+            }
+            "go" => {
+                self.cancel_search();
+                self.force = false;
+                self.engine_color = self.board.turn();
+                if game_result(&self.board).is_none() {
+                    self.think();
+                }
+            }
+            "playother" => {
+                self.cancel_search();
+                self.force = false;
+                self.engine_color = self.board.turn().opposite();
+            }
+            "usermove" => {
+                self.finish_search();
+                let text = tokens.next().unwrap_or("");
+                match self.board.parse_uci_move(text) {
+                    Ok(action) => {
+                        self.board.move_piece(action);
+                        if let Some(result) = game_result(&self.board) {
+                            self.output.line(result);
+                        } else if !self.force && self.board.turn() == self.engine_color {
+                            self.think();
+                        }
+                    }
+                    Err(err) => self
+                        .output
+                        .line(format_args!("Illegal move ({}): {}", err, text)),
+                }
+            }
+            "?" => self.finish_search(),
+            "setboard" => {
+                self.cancel_search();
+                let fen: Vec<&str> = tokens.collect();
+                match Board::from_fen(&fen.join(" ")) {
+                    Ok(board) => self.board = board,
+                    Err(err) => self
+                        .output
+                        .line(format_args!("tellusererror Illegal position: {}", err)),
+                }
+            }
+            "level" => match Level::parse(tokens) {
+                Some(level) => {
+                    self.level = level;
+                    self.move_time = None;
+                }
+                None => self.output.line(format_args!("Error (bad level): {}", line)),
+            },
+            "st" => match tokens
+                .next()
+                .and_then(|seconds| seconds.parse::<f64>().ok())
+            {
+                Some(seconds) => self.move_time = Some(Duration::from_secs_f64(seconds.max(0.0))),
+                None => self.output.line(format_args!("Error (bad st): {}", line)),
+            },
+            "sd" => self.depth = tokens.next().and_then(|depth| depth.parse().ok()),
+            "time" => self.time = tokens.next().and_then(|time| time.parse().ok()),
+            "otim" => self.opponent_time = tokens.next().and_then(|time| time.parse().ok()),
+            "undo" => {
+                self.cancel_search();
+                self.board.undo();
+            }
+            "remove" => {
+                self.cancel_search();
+                self.board.undo();
+                self.board.undo();
+            }
+            "post" => self.post = true,
+            "nopost" => self.post = false,
+            "result" => {
+                self.cancel_search();
+                self.force = true;
+            }
+            "memory" => {
+                if let Some(megabytes) = tokens.next().and_then(|mb| mb.parse::<usize>().ok()) {
+                    self.cancel_search();
+                    self.table.resize(megabytes.clamp(1, MAX_HASH_MB));
+                }
+            }
+            "ping" => self
+                .output
+                .line(format_args!("pong {}", tokens.next().unwrap_or(""))),
+            "quit" => return false,
+            // xboard, accepted, rejected, hard, easy, random, computer, name and the rest
+            _ => {}
+        }
+        true
+    }
+
+    fn search_limits(&self) -> SearchLimits {
+        if let Some(move_time) = self.move_time {
+            return SearchLimits {
+                depth: self.depth,
+                nodes: None,
+                time: Some(move_time),
+            };
+        }
+        // the same budgeting uci uses, with the clocks filled in from what xboard told us
+        let millis = |centis: Option<u64>| centis.map(|centis| centis * 10);
+        let increment = Some(self.level.increment.as_millis() as u64);
+        let moves_played = (self.board.fullmove_number() - 1) as u64;
+        let movestogo = match self.level.moves {
+            0 => None,
+            moves => Some(moves as u64 - moves_played % moves as u64),
+        };
+        let (mine, theirs) = (millis(self.time), millis(self.opponent_time));
+        let (wtime, btime) = match self.engine_color {
+            Color::White => (mine, theirs),
+            Color::Black => (theirs, mine),
+        };
+        let limits = GoLimits {
+            depth: self.depth,
+            wtime: wtime.or(Some(self.level.base.as_millis() as u64)),
+            btime: btime.or(Some(self.level.base.as_millis() as u64)),
+            winc: increment,
+            binc: increment,
+            movestogo,
+            ..Default::default()
+        };
+        let mut limits = limits.search_limits(self.engine_color);
+        // a level of 0 minutes means no clock at all
+        if self.level.base.is_zero() && self.time.is_none() {
+            limits.time = None;
+        }
+        limits
+    }
+    fn think(&mut self) {
+        let limits = self.search_limits();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (send_move, moves) = mpsc::channel();
+        let table = mem::take(&mut self.table);
+        let mut board = self.board.clone();
+        let post = self.post;
+        let output = self.output.clone();
+        let thread_cancel = cancel.clone();
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            let mut search = Search::with_table(limits, thread_stop, table);
+            let root = board.clone();
+            let result = search.run(&mut board, |result| {
+                if post {
+                    print_thinking(&output, &root, result);
+                }
+            });
+            if thread_cancel.load(Ordering::Relaxed) {
+                return search.into_table();
+            }
+            if let Some(action) = result.best_move {
+                let _ = send_move.send(action.clone());
+                output.line(format_args!("move {}", action.to_uci()));
+                board.move_piece(action);
+                if let Some(result) = game_result(&board) {
+                    output.line(result);
+                }
+            }
+            search.into_table()
+        });
+        self.search = Some(SearchHandle {
+            cancel,
+            stop,
+            moves,
+            thread,
+        });
+    }
+    // plays the engine's move if it is out, the search is as good as over by then
+    fn collect_move(&mut self) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        if let Ok(action) = search.moves.try_recv() {
+            self.board.move_piece(action);
+            self.end_search(false);
+        }
+    }
+    // makes the engine move now
+    fn finish_search(&mut self) {
+        self.end_search(false);
+    }
+    // stops the search without sending its move, unless it already went out
+    fn cancel_search(&mut self) {
+        self.end_search(true);
+    }
+    fn end_search(&mut self, cancel: bool) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        search.cancel.store(cancel, Ordering::Relaxed);
+        search.stop.store(true, Ordering::Relaxed);
+        self.table = search
+            .thread
+            .join()
+            .unwrap_or_else(|_| TranspositionTable::new(DEFAULT_HASH_MB));
+        // once the move is out the gui has seen it, so the board has to play it too
+        if let Ok(action) = search.moves.try_recv() {
+            self.board.move_piece(action);
+        }
+    }
+}
+
+impl Default for Xboard {
+    fn default() -> Self {
+        Xboard::new()
+    }
+}
+
+// what to tell the gui when the game is over
+fn game_result(board: &Board) -> Option<&'static str> {
+    let result = match board.game_status() {
+        GameStatus::Ongoing => return None,
+        GameStatus::Checkmate {
+            winner: Color::White,
+        } => "1-0 {White mates}",
+        GameStatus::Checkmate {
+            winner: Color::Black,
+        } => "0-1 {Black mates}",
+        GameStatus::Stalemate => "1/2-1/2 {Stalemate}",
+        GameStatus::ThreefoldRepetition | GameStatus::FivefoldRepetition => {
+            "1/2-1/2 {Draw by repetition}"
+        }
+        GameStatus::FiftyMoveRule | GameStatus::SeventyFiveMoveRule => {
+            "1/2-1/2 {Draw by fifty move rule}"
+        }
+        GameStatus::InsufficientMaterial => "1/2-1/2 {Insufficient material}",
+    };
+    Some(result)
+}
+
+// ply score time nodes pv, with the time in centiseconds and mates as 100000 plus the moves
+fn print_thinking(output: &Output, root: &Board, result: &SearchResult) {
+    let score = match result.mate_in() {
+        Some(moves) if moves > 0 => 100_000 + moves,
+        Some(moves) => -100_000 + moves,
+        None => result.score,
+    };
+    let mut board = root.clone();
+    let mut pv = Vec::new();
+    for action in &result.pv {
+        pv.push(action.to_san(&board));
+        board.move_piece(action.clone());
+    }
+    output.line(format_args!(
+        "{} {} {} {} {}",
+        result.depth,
+        score,
+        result.elapsed.as_millis() / 10,
+        result.nodes,
+        pv.join(" ")
+    ));
+}
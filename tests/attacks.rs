@@ -0,0 +1,64 @@
+use rusttesting::chess_engine::{
+    board::{
+        bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+        Bitboard, BoardPosition,
+    },
+    pieces::Color,
+};
+
+fn square(name: &str) -> u8 {
+    name.parse::<BoardPosition>().unwrap().square()
+}
+fn squares(names: &[&str]) -> Bitboard {
+    let mut bitboard = Bitboard::EMPTY;
+    for name in names {
+        bitboard.set(square(name));
+    }
+    bitboard
+}
+
+#[test]
+fn knights_and_kings() {
+    assert_eq!(knight_attacks(square("a1")), squares(&["b3", "c2"]));
+    assert_eq!(knight_attacks(square("e4")).count(), 8);
+    assert_eq!(
+        knight_attacks(square("h8")),
+        squares(&["g6", "f7"]),
+        "no wrapping onto the a file"
+    );
+    assert_eq!(king_attacks(square("a1")), squares(&["a2", "b1", "b2"]));
+    assert_eq!(king_attacks(square("e4")).count(), 8);
+    assert_eq!(king_attacks(square("h5")).count(), 5);
+}
+
+#[test]
+fn pawns_attack_forwards_diagonally() {
+    assert_eq!(pawn_attacks(Color::White, square("e4")), squares(&["d5", "f5"]));
+    assert_eq!(pawn_attacks(Color::Black, square("e4")), squares(&["d3", "f3"]));
+    assert_eq!(pawn_attacks(Color::White, square("a2")), squares(&["b3"]));
+    assert_eq!(pawn_attacks(Color::Black, square("h7")), squares(&["g6"]));
+    assert!(pawn_attacks(Color::White, square("c8")).is_empty());
+}
+
+#[test]
+fn sliders_stop_at_the_first_blocker() {
+    assert_eq!(rook_attacks(square("a1"), Bitboard::EMPTY).count(), 14);
+    assert_eq!(bishop_attacks(square("d4"), Bitboard::EMPTY).count(), 13);
+    assert_eq!(queen_attacks(square("d4"), Bitboard::EMPTY).count(), 27);
+
+    let blockers = squares(&["a4", "c1"]);
+    assert_eq!(
+        rook_attacks(square("a1"), blockers),
+        squares(&["a2", "a3", "a4", "b1", "c1"])
+    );
+    let blockers = squares(&["f6", "b2"]);
+    assert_eq!(
+        bishop_attacks(square("d4"), blockers),
+        squares(&["e5", "f6", "c3", "b2", "c5", "b6", "a7", "e3", "f2", "g1"])
+    );
+    let occupied = squares(&["d6", "f6"]);
+    assert_eq!(
+        queen_attacks(square("d4"), occupied),
+        rook_attacks(square("d4"), occupied) | bishop_attacks(square("d4"), occupied)
+    );
+}
@@ -0,0 +1,76 @@
+use rusttesting::chess_engine::{
+    board::{Board, CastleSide},
+    pieces::Color,
+};
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board
+            .legal_moves()
+            .into_iter()
+            .find(|action| action.to_string() == *text)
+            .unwrap();
+        board.move_piece(action);
+    }
+}
+fn can_play(fen: &str, text: &str) -> bool {
+    Board::from_fen(fen)
+        .unwrap()
+        .legal_moves()
+        .iter()
+        .any(|action| action.to_string() == text)
+}
+
+#[test]
+fn castling_moves_king_and_rook() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    let mut board = Board::from_fen(fen).unwrap();
+    play(&mut board, &["e1g1"]);
+    assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1");
+    play(&mut board, &["e8c8"]);
+    assert_eq!(board.to_fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+    let castle = board.history().last().unwrap();
+    assert_eq!(castle.castle_side(), Some(CastleSide::Queenside));
+}
+
+#[test]
+fn no_castling_out_of_through_or_into_check() {
+    // the rook on e8 gives check
+    assert!(!can_play("4r3/8/2k5/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"));
+    // f1 is attacked
+    assert!(!can_play("5r2/8/2k5/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"));
+    assert!(can_play("5r2/8/2k5/8/8/8/8/R3K2R w KQ - 0 1", "e1c1"));
+    // g1 is attacked
+    assert!(!can_play("6r1/8/2k5/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"));
+    // b1 may be attacked, the king doesn't cross it
+    assert!(can_play("1r6/8/2k5/8/8/8/8/R3K2R w KQ - 0 1", "e1c1"));
+    // but it may not be blocked
+    assert!(!can_play("8/8/2k5/8/8/8/8/RN2K2R w KQ - 0 1", "e1c1"));
+}
+
+#[test]
+fn rights_go_with_king_and_rook_moves() {
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    play(&mut board, &["h1h2"]);
+    assert!(!board.castling_rights().has(Color::White, CastleSide::Kingside));
+    assert!(board.castling_rights().has(Color::White, CastleSide::Queenside));
+    play(&mut board, &["e8d8"]);
+    assert!(!board.castling_rights().has(Color::Black, CastleSide::Kingside));
+    assert!(!board.castling_rights().has(Color::Black, CastleSide::Queenside));
+    assert_eq!(board.to_fen(), "r2k3r/8/8/8/8/8/7R/R3K3 w Q - 2 2");
+}
+
+#[test]
+fn capturing_a_rook_takes_its_right() {
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/6B1/R3K2R w KQkq - 0 1").unwrap();
+    play(&mut board, &["g2a8"]);
+    assert!(!board.castling_rights().has(Color::Black, CastleSide::Queenside));
+    assert!(board.castling_rights().has(Color::Black, CastleSide::Kingside));
+    assert!(!board
+        .legal_moves()
+        .iter()
+        .any(|action| action.to_string() == "e8c8"));
+    // and undoing the capture gives it back
+    board.undo();
+    assert!(board.castling_rights().has(Color::Black, CastleSide::Queenside));
+}
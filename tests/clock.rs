@@ -0,0 +1,135 @@
+use std::{sync::Arc, time::Duration};
+
+use rusttesting::chess_engine::{
+    clock::{Clock, Delay, ManualTime, Stage, TimeControl},
+    game::{Game, Termination},
+    pgn::GameResult,
+    pieces::Color,
+};
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+fn clock(control: TimeControl) -> (Clock, ManualTime) {
+    let time = ManualTime::new();
+    let mut clock = Clock::with_time_source(control, Arc::new(time.clone()));
+    clock.start(Color::White);
+    (clock, time)
+}
+
+#[test]
+fn sudden_death_counts_down_and_flags() {
+    let (mut clock, time) = clock(TimeControl::sudden_death(secs(60)));
+    time.advance(secs(20));
+    assert_eq!(clock.remaining(Color::White), secs(40));
+    clock.press().unwrap();
+    assert_eq!(clock.running(), Some(Color::Black));
+    time.advance(secs(59));
+    assert_eq!(clock.remaining(Color::Black), secs(1));
+    assert_eq!(clock.remaining(Color::White), secs(40));
+    assert_eq!(clock.flagged(), None);
+    time.advance(secs(1));
+    assert_eq!(clock.flagged(), Some(Color::Black));
+    assert_eq!(clock.press(), Err(Color::Black));
+}
+
+#[test]
+fn fischer_adds_the_increment() {
+    let (mut clock, time) = clock(TimeControl::fischer(secs(60), secs(2)));
+    time.advance(secs(10));
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(52));
+}
+
+#[test]
+fn bronstein_gives_back_at_most_the_delay() {
+    let (mut clock, time) = clock(TimeControl::bronstein(secs(60), secs(5)));
+    time.advance(secs(3));
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(60));
+    time.advance(secs(10));
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::Black), secs(55));
+}
+
+#[test]
+fn simple_delay_waits_before_counting() {
+    let (mut clock, time) = clock(TimeControl::simple_delay(secs(60), secs(5)));
+    time.advance(secs(4));
+    assert_eq!(clock.remaining(Color::White), secs(60));
+    time.advance(secs(4));
+    assert_eq!(clock.remaining(Color::White), secs(57));
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(57));
+}
+
+#[test]
+fn stages_add_time_after_their_moves() {
+    let control: TimeControl = "2/60+1:30".parse().unwrap();
+    assert_eq!(
+        control.stages(),
+        &[
+            Stage { moves: Some(2), time: secs(60), delay: Delay::Fischer(secs(1)) },
+            Stage { moves: None, time: secs(30), delay: Delay::None },
+        ]
+    );
+    assert_eq!(control.to_string(), "2/60+1:30");
+    assert!("40/".parse::<TimeControl>().is_err());
+    assert!("".parse::<TimeControl>().is_err());
+
+    let (mut clock, time) = clock(control);
+    for _ in 0..2 {
+        time.advance(secs(10));
+        clock.press().unwrap();
+        clock.press().unwrap();
+    }
+    // two moves of ten seconds, two increments and the second stage
+    assert_eq!(clock.remaining(Color::White), secs(60 - 20 + 2 + 30));
+    assert_eq!(clock.remaining(Color::Black), secs(60 + 2 + 30));
+    time.advance(secs(10));
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(62));
+}
+
+#[test]
+fn repeating_stages() {
+    let (mut clock, _) = clock("1/10".parse().unwrap());
+    clock.press().unwrap();
+    clock.press().unwrap();
+    clock.press().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(30));
+}
+
+#[test]
+fn flag_fall_ends_the_game() {
+    let time = ManualTime::new();
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::sudden_death(secs(60)), Arc::new(time.clone())));
+    game.play_san("e4").unwrap();
+    time.advance(secs(61));
+    assert!(game.play_san("e5").is_err());
+    assert_eq!(game.result(), GameResult::WhiteWins);
+    assert_eq!(game.termination(), Some(Termination::TimeForfeit));
+    assert_eq!(game.clock().unwrap().running(), None);
+    assert_eq!(game.to_pgn().tag("TimeControl"), Some("60"));
+    assert_eq!(game.to_pgn().tag("Termination"), Some("time forfeit"));
+}
+
+#[test]
+fn timeout_against_a_bare_king_is_a_draw() {
+    let time = ManualTime::new();
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    game.set_clock(Clock::with_time_source(TimeControl::sudden_death(secs(60)), Arc::new(time.clone())));
+    game.play_san("e3").unwrap();
+    time.advance(secs(61));
+    assert!(game.check_time());
+    assert_eq!(game.result(), GameResult::WhiteWins);
+
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    game.set_clock(Clock::with_time_source(TimeControl::sudden_death(secs(60)), Arc::new(time.clone())));
+    time.advance(secs(61));
+    assert!(game.check_time());
+    assert_eq!(game.result(), GameResult::Draw);
+    assert_eq!(game.termination(), Some(Termination::TimeoutVsInsufficientMaterial));
+    assert_eq!(game.to_pgn().tag("Termination"), Some("time forfeit"));
+}
@@ -0,0 +1,46 @@
+// not every test file uses every helper
+#![allow(dead_code)]
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rusttesting::output::Output;
+
+// everything a front end wrote, shared with the test
+#[derive(Clone, Default)]
+pub struct Buffer(Arc<Mutex<Vec<u8>>>);
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+impl Buffer {
+    pub fn output(&self) -> Output {
+        Output::new(self.clone())
+    }
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+    // searches answer from their own thread, so wait until what they wrote passes `done`
+    pub fn wait_until<T>(&self, mut done: impl FnMut(&str) -> Option<T>) -> T {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(30) {
+            if let Some(found) = done(&self.text()) {
+                return found;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("gave up waiting, the output so far is\n{}", self.text());
+    }
+}
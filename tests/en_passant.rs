@@ -0,0 +1,67 @@
+use rusttesting::chess_engine::board::{Board, BoardPosition};
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board
+            .legal_moves()
+            .into_iter()
+            .find(|action| action.to_string() == *text)
+            .unwrap();
+        board.move_piece(action);
+    }
+}
+fn can_play(board: &Board, text: &str) -> bool {
+    board
+        .legal_moves()
+        .iter()
+        .any(|action| action.to_string() == text)
+}
+
+#[test]
+fn double_step_sets_the_square() {
+    let mut board = Board::new();
+    play(&mut board, &["e2e4"]);
+    assert_eq!(board.en_passant(), Some("e3".parse::<BoardPosition>().unwrap()));
+    play(&mut board, &["g8f6"]);
+    assert_eq!(board.en_passant(), None);
+}
+
+#[test]
+fn capture_and_take_back() {
+    let mut board = Board::new();
+    play(&mut board, &["e2e4", "a7a6", "e4e5", "d7d5"]);
+    let before = board.to_fen();
+    let hash = board.hash();
+    let action = board
+        .legal_moves()
+        .into_iter()
+        .find(|action| action.to_string() == "e5d6")
+        .unwrap();
+    assert!(action.is_en_passant());
+    assert!(action.is_capture());
+    board.move_piece(action);
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+    );
+    board.undo();
+    assert_eq!(board.to_fen(), before);
+    assert_eq!(board.hash(), hash);
+}
+
+#[test]
+fn only_right_after_the_double_step() {
+    let mut board = Board::new();
+    play(&mut board, &["e2e4", "a7a6", "e4e5", "d7d5"]);
+    assert!(can_play(&board, "e5d6"));
+    play(&mut board, &["h2h3", "h7h6"]);
+    assert!(!can_play(&board, "e5d6"));
+}
+
+#[test]
+fn not_when_it_uncovers_the_king() {
+    // taking would clear the fifth rank between the rook and the king
+    let board = Board::from_fen("8/8/8/K2pP2r/8/8/8/4k3 w - d6 0 1").unwrap();
+    assert!(!can_play(&board, "e5d6"));
+    assert!(can_play(&board, "e5e6"));
+}
@@ -0,0 +1,115 @@
+use rusttesting::chess_engine::{
+    board::{Board, STARTING_FEN},
+    eval::{phase, EvalTerm, Evaluator, TaperedScore, MAX_PHASE},
+};
+
+const POSITIONS: [&str; 4] = [
+    "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    "r3k2r/pbppqpb1/1pn3p1/7p/1N2pPn1/1PP4N/PB1P2PP/R2QKB1R b KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "4k3/8/8/8/3Q4/8/8/4K3 w - - 0 1",
+];
+
+fn evaluate(fen: &str) -> i32 {
+    Evaluator::default().evaluate(&Board::from_fen(fen).unwrap())
+}
+// the same position with the colours swapped and the board turned upside down
+fn mirror(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let swap_case = |text: &str| -> String {
+        text.chars()
+            .map(|c| match c.is_ascii_uppercase() {
+                true => c.to_ascii_lowercase(),
+                false => c.to_ascii_uppercase(),
+            })
+            .collect()
+    };
+    let placement: Vec<String> = fields[0].split('/').rev().map(swap_case).collect();
+    let turn = match fields[1] {
+        "w" => "b",
+        _ => "w",
+    };
+    let castling = match fields[2] {
+        "-" => "-".to_string(),
+        rights => {
+            let swapped = swap_case(rights);
+            // white's rights come first
+            let (white, black): (String, String) = swapped.chars().partition(char::is_ascii_uppercase);
+            white + &black
+        }
+    };
+    format!("{} {} {} - {} {}", placement.join("/"), turn, castling, fields[4], fields[5])
+}
+
+struct Fixed(TaperedScore);
+impl EvalTerm for Fixed {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+    fn evaluate(&self, _: &Board) -> TaperedScore {
+        self.0
+    }
+}
+
+#[test]
+fn the_start_is_level() {
+    assert_eq!(evaluate(STARTING_FEN), 0);
+}
+
+#[test]
+fn the_sign_follows_the_side_to_move() {
+    for fen in POSITIONS {
+        let other_side = match fen.contains(" w ") {
+            true => fen.replace(" w ", " b "),
+            false => fen.replace(" b ", " w "),
+        };
+        assert_eq!(evaluate(fen), -evaluate(&other_side), "{}", fen);
+    }
+    assert!(evaluate("4k3/8/8/8/3Q4/8/8/4K3 w - - 0 1") > 500);
+    assert!(evaluate("4k3/8/8/8/3Q4/8/8/4K3 b - - 0 1") < -500);
+}
+
+#[test]
+fn mirrored_positions_score_the_same() {
+    for fen in POSITIONS {
+        let mirrored = mirror(fen);
+        assert_eq!(evaluate(fen), evaluate(&mirrored), "{} against {}", fen, mirrored);
+        let evaluator = Evaluator::default();
+        let breakdown = evaluator.breakdown(&Board::from_fen(fen).unwrap());
+        let mirrored_breakdown = evaluator.breakdown(&Board::from_fen(&mirrored).unwrap());
+        for ((name, score), (_, mirrored_score)) in breakdown.iter().zip(&mirrored_breakdown) {
+            assert_eq!(*score, -*mirrored_score, "{} in {}", name, fen);
+        }
+    }
+}
+
+#[test]
+fn phase_counts_the_pieces() {
+    let phase_of = |fen: &str| phase(&Board::from_fen(fen).unwrap());
+    assert_eq!(phase_of(STARTING_FEN), MAX_PHASE);
+    assert_eq!(phase_of("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1"), 0);
+    assert_eq!(phase_of("3qk3/8/8/8/8/8/8/R2NK3 w - - 0 1"), 4 + 2 + 1);
+    // extra queens from promotions don't push it past the opening
+    assert_eq!(phase_of("4k3/8/8/8/8/8/8/QQQQKQQQ w - - 0 1"), MAX_PHASE);
+}
+
+#[test]
+fn taper_blends_from_middlegame_to_endgame() {
+    let score = TaperedScore::new(100, -100);
+    assert_eq!(score.taper(MAX_PHASE), 100);
+    assert_eq!(score.taper(0), -100);
+    assert_eq!(score.taper(MAX_PHASE / 2), 0);
+    assert_eq!(score.taper(MAX_PHASE * 3 / 4), 50);
+    assert_eq!(score.taper(MAX_PHASE + 10), 100);
+    assert_eq!(score.taper(-5), -100);
+
+    // the evaluator tapers with the phase of the board it looks at
+    let mut evaluator = Evaluator::empty();
+    evaluator.add_term(Fixed(score));
+    let full = Board::new();
+    assert_eq!(evaluator.evaluate(&full), 100);
+    let pawns = Board::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap();
+    assert_eq!(evaluator.evaluate(&pawns), -100);
+    let rooks = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b - - 0 1").unwrap();
+    assert_eq!(evaluator.evaluate(&rooks), -score.taper(8));
+}
@@ -0,0 +1,167 @@
+use rusttesting::chess_engine::{
+    board::{Board, STARTING_FEN},
+    errors::FenError,
+    pieces::Color,
+    Error,
+};
+
+fn fen_error(fen: &str) -> FenError {
+    match Board::from_fen(fen) {
+        Err(Error::Fen(err)) => err,
+        other => panic!(
+            "expected a fen error for {}, got {:?}",
+            fen,
+            other.map(|board| board.to_fen())
+        ),
+    }
+}
+
+#[test]
+fn round_trips() {
+    for fen in [
+        STARTING_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 12 40",
+    ] {
+        assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+    }
+    // the counters may be left out
+    assert_eq!(
+        Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -")
+            .unwrap()
+            .to_fen(),
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1"
+    );
+}
+
+#[test]
+fn broken_fields() {
+    assert!(matches!(fen_error(""), FenError::MissingField(_)));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4K3 w -"),
+        FenError::MissingField(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4K3 w - - 0 1 x"),
+        FenError::TooManyFields(7)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::WrongRankCount(7)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4X3 w - - 0 1"),
+        FenError::InvalidPiece('X')
+    ));
+    assert!(matches!(
+        fen_error("4k3/9/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidRank(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/7/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidRank(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/08/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidRank(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"),
+        FenError::InvalidTurn(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4K3 w KX - 0 1"),
+        FenError::InvalidCastling(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/4K3 w - - x 1"),
+        FenError::InvalidCounter(_)
+    ));
+}
+
+#[test]
+fn one_king_each() {
+    assert!(matches!(
+        fen_error("8/8/8/8/8/8/8/8 w - - 0 1"),
+        FenError::KingCount(Color::White, 0)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/8 w - - 0 1"),
+        FenError::KingCount(Color::White, 0)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"),
+        FenError::KingCount(Color::White, 2)
+    ));
+    assert!(matches!(
+        fen_error("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::KingCount(Color::Black, 0)
+    ));
+}
+
+#[test]
+fn the_side_that_just_moved_is_not_in_check() {
+    assert!(matches!(
+        fen_error("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::KingCanBeTaken(Color::Black)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/8/8/8/q3K3 b - - 0 1"),
+        FenError::KingCanBeTaken(Color::White)
+    ));
+    // being in check with the move is fine
+    let board = Board::from_fen("4k3/4R3/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert!(board.is_in_check(Color::Black));
+}
+
+#[test]
+fn en_passant_has_to_be_behind_a_pawn_that_just_moved() {
+    // no pawn on e4
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3p4/8/8/4K3 b - e3 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+    // wrong rank
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3p4/8/8/4K3 b - e1 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+    // the pawn belongs to the side to move
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3pp3/8/8/4K3 b - e3 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+    // white to move needs the square on the sixth rank
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3pP3/8/8/4K3 w - e3 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+    // the square has to be empty
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3pP3/4N3/8/4K3 b - e3 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+    assert!(matches!(
+        fen_error("4k3/8/8/8/3pP3/8/8/4K3 b - z9 0 1"),
+        FenError::InvalidEnPassant(_)
+    ));
+
+    let board = Board::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+    let capture = board.parse_uci_move("d4e3").unwrap();
+    assert!(capture.is_en_passant());
+    assert_eq!(board.legal_moves().len(), 7);
+}
+
+#[test]
+fn errors_read_as_text() {
+    let err = Board::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(err.to_string(), "invalid fen, white has 0 kings instead of 1");
+    let err = Board::from_fen("4k3/8/8/8/3p4/8/8/4K3 b - e3 0 1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid fen, `e3` can't be the en passant square here"
+    );
+    let err = Board::new().parse_uci_move("e2e5").unwrap_err();
+    assert_eq!(err.to_string(), "`e2e5` is not a legal move here");
+}
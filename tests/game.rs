@@ -0,0 +1,109 @@
+use rusttesting::chess_engine::{
+    board::GameStatus,
+    errors::GameError,
+    game::{Game, Termination},
+    pgn::{GameResult, PgnReader},
+    pieces::Color,
+    Error,
+};
+
+fn play(game: &mut Game, moves: &[&str]) {
+    for san in moves {
+        game.play_san(san).unwrap();
+    }
+}
+
+#[test]
+fn checkmate_ends_the_game() {
+    let mut game = Game::new();
+    play(&mut game, &["f3", "e5", "g4"]);
+    assert_eq!(game.play_san("Qh4#").unwrap(), GameStatus::Checkmate { winner: Color::Black });
+    assert!(game.is_over());
+    assert_eq!(game.result(), GameResult::BlackWins);
+    assert_eq!(game.termination(), Some(Termination::Checkmate));
+    assert_eq!(game.history().len(), 4);
+    assert!(matches!(game.play_san("Kf2"), Err(Error::Game(GameError::GameOver))));
+}
+
+#[test]
+fn rejects_illegal_moves() {
+    let mut game = Game::new();
+    let other = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+    let castle = other.board().parse_san("O-O-O").unwrap();
+    assert!(matches!(game.play(&castle), Err(Error::Game(GameError::IllegalMove(_)))));
+    assert!(game.history().is_empty());
+}
+
+#[test]
+fn resigning() {
+    let mut game = Game::new();
+    play(&mut game, &["e4"]);
+    game.resign(Color::Black).unwrap();
+    assert_eq!(game.result(), GameResult::WhiteWins);
+    assert_eq!(game.termination(), Some(Termination::Resignation));
+    assert!(matches!(game.resign(Color::White), Err(Error::Game(GameError::GameOver))));
+}
+
+#[test]
+fn draw_offers() {
+    let mut game = Game::new();
+    assert!(matches!(game.accept_draw(Color::Black), Err(Error::Game(GameError::NoDrawOffer))));
+    game.offer_draw(Color::White).unwrap();
+    // the offer stands while white moves, but black moving turns it down
+    play(&mut game, &["e4"]);
+    assert_eq!(game.draw_offer(), Some(Color::White));
+    assert!(matches!(game.accept_draw(Color::White), Err(Error::Game(GameError::NoDrawOffer))));
+    play(&mut game, &["e5"]);
+    assert_eq!(game.draw_offer(), None);
+    game.offer_draw(Color::White).unwrap();
+    game.accept_draw(Color::Black).unwrap();
+    assert_eq!(game.result(), GameResult::Draw);
+    assert_eq!(game.termination(), Some(Termination::DrawAgreement));
+}
+
+#[test]
+fn claiming_and_automatic_draws() {
+    let mut game = Game::new();
+    assert!(matches!(game.claim_draw(), Err(Error::Game(GameError::NoDrawToClaim))));
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+    play(&mut game, &shuffle);
+    play(&mut game, &shuffle);
+    assert_eq!(game.status(), GameStatus::ThreefoldRepetition);
+    assert!(!game.is_over());
+    play(&mut game, &shuffle);
+    play(&mut game, &shuffle);
+    assert!(game.is_over());
+    assert_eq!(game.termination(), Some(Termination::FivefoldRepetition));
+
+    let mut game = Game::new();
+    play(&mut game, &shuffle);
+    play(&mut game, &shuffle);
+    game.claim_draw().unwrap();
+    assert_eq!(game.termination(), Some(Termination::ThreefoldRepetition));
+
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1").unwrap();
+    play(&mut game, &["Kxd2"]);
+    assert_eq!(game.termination(), Some(Termination::InsufficientMaterial));
+}
+
+#[test]
+fn pgn_round_trip() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    game.info.white = Some("Alice".to_string());
+    game.info.event = Some("Club night".to_string());
+    play(&mut game, &["e4", "Kd7"]);
+    game.resign(Color::Black).unwrap();
+    let pgn = game.to_pgn();
+    assert_eq!(pgn.tag("White"), Some("Alice"));
+    assert_eq!(pgn.tag("Termination"), Some("normal"));
+    let text = pgn.to_string();
+    assert!(text.contains("1. e4 Kd7 1-0"), "{}", text);
+
+    let read = PgnReader::new(text.as_bytes()).next().unwrap().unwrap();
+    let loaded = Game::from_pgn(&read).unwrap();
+    assert_eq!(loaded.info.white.as_deref(), Some("Alice"));
+    assert_eq!(loaded.info.black, None);
+    assert_eq!(loaded.result(), GameResult::WhiteWins);
+    assert_eq!(loaded.board().to_fen(), game.board().to_fen());
+    assert_eq!(loaded.starting_position().to_fen(), "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+}
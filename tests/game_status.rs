@@ -0,0 +1,102 @@
+use rusttesting::chess_engine::{
+    board::{Board, GameStatus},
+    pieces::Color,
+};
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board
+            .legal_moves()
+            .into_iter()
+            .find(|action| action.to_string() == *text)
+            .unwrap();
+        board.move_piece(action);
+    }
+}
+fn status(fen: &str) -> GameStatus {
+    Board::from_fen(fen).unwrap().game_status()
+}
+
+#[test]
+fn mate_and_stalemate() {
+    let mut board = Board::new();
+    play(&mut board, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+    assert_eq!(board.game_status(), GameStatus::Checkmate { winner: Color::Black });
+    assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), GameStatus::Stalemate);
+    assert_eq!(Board::new().game_status(), GameStatus::Ongoing);
+    // mate on the hundredth halfmove still counts as mate
+    assert_eq!(
+        status("7k/6Q1/6K1/8/8/8/8/8 b - - 100 80"),
+        GameStatus::Checkmate { winner: Color::White }
+    );
+}
+
+#[test]
+fn repetitions() {
+    let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+    let mut board = Board::new();
+    play(&mut board, &shuffle);
+    assert_eq!(board.repetitions(), 2);
+    assert_eq!(board.game_status(), GameStatus::Ongoing);
+    play(&mut board, &shuffle);
+    assert_eq!(board.game_status(), GameStatus::ThreefoldRepetition);
+    play(&mut board, &shuffle);
+    assert_eq!(board.repetitions(), 4);
+    assert_eq!(board.game_status(), GameStatus::ThreefoldRepetition);
+    play(&mut board, &shuffle);
+    assert_eq!(board.repetitions(), 5);
+    assert_eq!(board.game_status(), GameStatus::FivefoldRepetition);
+    board.undo();
+    assert_eq!(board.repetitions(), 4);
+    assert!(board.game_status().is_draw());
+    // a pawn move means none of the earlier positions can come back
+    play(&mut board, &["f6g8", "e2e4"]);
+    assert_eq!(board.repetitions(), 1);
+}
+
+#[test]
+fn move_counters() {
+    let fen = |clock: u32| format!("4k3/8/8/8/8/8/4P3/R3K3 w - - {} 90", clock);
+    assert_eq!(status(&fen(99)), GameStatus::Ongoing);
+    assert_eq!(status(&fen(100)), GameStatus::FiftyMoveRule);
+    assert_eq!(status(&fen(150)), GameStatus::SeventyFiveMoveRule);
+    let mut board = Board::from_fen(&fen(99)).unwrap();
+    play(&mut board, &["e2e3"]);
+    assert_eq!(board.game_status(), GameStatus::Ongoing);
+}
+
+#[test]
+fn insufficient_material() {
+    let drawn = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2N1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 b - - 0 1",
+        "2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1",
+    ];
+    for fen in drawn {
+        assert_eq!(status(fen), GameStatus::InsufficientMaterial, "{}", fen);
+    }
+    let playable = [
+        "3bk3/8/8/8/8/8/8/3BK3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/7p/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/3RK3 w - - 0 1",
+    ];
+    for fen in playable {
+        assert_eq!(status(fen), GameStatus::Ongoing, "{}", fen);
+    }
+}
+
+#[test]
+fn can_checkmate_agrees_with_insufficient_material() {
+    let can = |fen: &str, color: Color| Board::from_fen(fen).unwrap().can_checkmate(color);
+    // two bishops on the same colour can never mate
+    let same_colour = "4k3/8/8/8/8/8/8/B1B1K3 w - - 0 1";
+    assert_eq!(status(same_colour), GameStatus::InsufficientMaterial);
+    assert!(!can(same_colour, Color::White));
+    assert!(!can(same_colour, Color::Black));
+    assert!(can("4k3/8/8/8/8/8/8/1BB1K3 w - - 0 1", Color::White));
+    // a lone knight can still mate if the other side has something to block with
+    assert!(!can("4k3/8/8/8/8/8/8/2N1K3 w - - 0 1", Color::White));
+    assert!(can("4k3/4p3/8/8/8/8/8/2N1K3 w - - 0 1", Color::White));
+}
@@ -0,0 +1,90 @@
+use rusttesting::chess_engine::board::Board;
+
+fn play(board: &mut Board, text: &str) {
+    let action = board
+        .legal_moves()
+        .into_iter()
+        .find(|action| action.to_string() == text)
+        .unwrap();
+    board.move_piece(action);
+}
+
+// castling, en passant, a capture and a promotion, everything undo has to put back
+const MOVES: [&str; 13] = [
+    "e2e4", "g8f6", "e4e5", "d7d5", "e5d6", "e7d6", "g1f3", "f8e7", "f1c4", "e8g8", "e1g1",
+    "b7b5", "c4b5",
+];
+
+#[test]
+fn undo_and_redo_restore_fen_and_hash() {
+    let mut board = Board::new();
+    let mut seen = vec![(board.to_fen(), board.hash())];
+    for text in MOVES {
+        play(&mut board, text);
+        seen.push((board.to_fen(), board.hash()));
+    }
+    for expected in seen.iter().rev().skip(1) {
+        assert!(board.undo().is_some());
+        assert_eq!(&(board.to_fen(), board.hash()), expected);
+    }
+    assert!(board.undo().is_none());
+    assert!(board.history().is_empty());
+    for expected in seen.iter().skip(1) {
+        assert!(board.redo().is_some());
+        assert_eq!(&(board.to_fen(), board.hash()), expected);
+    }
+    assert!(board.redo().is_none());
+    assert_eq!(board.history().len(), MOVES.len());
+}
+
+#[test]
+fn promotion_undo_and_redo() {
+    let mut board = Board::from_fen("1r5k/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let start = board.to_fen();
+    play(&mut board, "a7b8r");
+    let promoted = board.to_fen();
+    board.undo();
+    assert_eq!(board.to_fen(), start);
+    board.redo();
+    assert_eq!(board.to_fen(), promoted);
+}
+
+#[test]
+fn a_new_move_drops_what_could_be_redone() {
+    let mut board = Board::new();
+    play(&mut board, "e2e4");
+    play(&mut board, "e7e5");
+    board.undo();
+    assert!(board.history().can_redo());
+    play(&mut board, "c7c5");
+    assert!(!board.history().can_redo());
+    assert!(board.redo().is_none());
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"
+    );
+}
+
+#[test]
+fn truncating_matches_playing_only_the_first_moves() {
+    for ply in 0..=MOVES.len() {
+        let mut board = Board::new();
+        for text in MOVES {
+            play(&mut board, text);
+        }
+        // something to redo, which has to go as well
+        board.undo();
+        board.truncate_to(ply);
+        // past the end nothing is taken back
+        let played = ply.min(MOVES.len() - 1);
+        let mut expected = Board::new();
+        for text in &MOVES[..played] {
+            play(&mut expected, text);
+        }
+        assert_eq!(board.to_fen(), expected.to_fen(), "at ply {}", ply);
+        assert_eq!(board.hash(), expected.hash(), "at ply {}", ply);
+        assert_eq!(board.history().len(), played);
+        assert!(!board.history().can_redo());
+        assert!(board.redo().is_none());
+    }
+}
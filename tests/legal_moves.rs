@@ -0,0 +1,51 @@
+use rusttesting::chess_engine::{board::Board, pieces::Color};
+
+fn moves(fen: &str) -> Vec<String> {
+    let mut moves: Vec<String> = Board::from_fen(fen)
+        .unwrap()
+        .legal_moves()
+        .iter()
+        .map(|action| action.to_string())
+        .collect();
+    moves.sort();
+    moves
+}
+
+#[test]
+fn twenty_moves_from_the_start() {
+    assert_eq!(Board::new().legal_moves().len(), 20);
+}
+
+#[test]
+fn pinned_pieces_stay_put() {
+    // the bishop on e2 shields the king from the rook
+    assert_eq!(
+        moves("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1"),
+        ["e1d1", "e1d2", "e1f1", "e1f2"]
+    );
+}
+
+#[test]
+fn check_has_to_be_answered() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1").unwrap();
+    assert!(board.is_in_check(Color::White));
+    assert!(!board.is_in_check(Color::Black));
+    assert_eq!(moves("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1"), ["e1d2", "e1f1"]);
+    // block or take, the knight can't wander off
+    assert_eq!(
+        moves("4k3/4r3/8/8/8/5N2/8/4K3 w - - 0 1"),
+        ["e1d1", "e1d2", "e1f1", "e1f2", "f3e5"]
+    );
+}
+
+#[test]
+fn the_king_keeps_out_of_attacks() {
+    // the queen checks along the first rank and the pawn on e3 covers d2 and f2
+    assert_eq!(moves("4k3/8/8/8/8/4p3/8/q3K3 w - - 0 1"), ["e1e2"]);
+}
+
+#[test]
+fn checkmate_and_stalemate_leave_no_moves() {
+    assert!(moves("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").is_empty());
+    assert!(moves("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").is_empty());
+}
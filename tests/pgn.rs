@@ -0,0 +1,242 @@
+use rusttesting::chess_engine::{
+    board::{Board, GameStatus},
+    errors::{PgnError, PgnErrorKind},
+    pgn::{GameResult, PgnGame, PgnReader},
+    pieces::Color,
+    Error,
+};
+
+const GAMES: &str = r#"[Event "Casual \"blitz\""]
+[Site "?"]
+[White "Anderssen"]
+[Black "Kieseritzky"]
+[Result "1-0"]
+
+% this line is hidden from readers
+{Opening comment} 1. e4 e5 2. f4 exf4 3. Bc4 Qh4+ 4. Kf1 b5?! $10 5. Bxb5 Nf6
+6. Nf3 Qh6 7. d3 Nh5 8. Nh4 Qg5 9. Nf5 c6 10. g4 Nf6 11. Rg1 cxb5 12. h4 Qg6
+13. h5 Qg5 14. Qf3 Ng8 15. Bxf4 Qf6 16. Nc3 Bc5 17. Nd5 Qxb2 18. Bd6 Bxg1
+(18... Qxa1+ 19. Ke2 Qb2 {a long comment
+that runs over two lines} (19... Qxg1 20. Nxg7+) 20. Kd2)
+19. e5 Qxa1+ 20. Ke2 Na6 21. Nxg7+ Kd8 22. Qf6+ Nxf6 23. Be7# 1-0
+
+[Event "Fool's mate"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 ; the worst move on the board
+Qh4# 0-1
+
+[Event "From a position"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+
+1. e4 Kd7 2. e5 *
+"#;
+
+fn games() -> Vec<PgnGame> {
+    PgnReader::new(GAMES.as_bytes()).collect::<Result<_, _>>().unwrap()
+}
+fn first_error(pgn: &str) -> PgnError {
+    let error = PgnReader::new(pgn.as_bytes())
+        .find_map(|game| game.err())
+        .expect("the pgn should not parse");
+    match error {
+        Error::Pgn(error) => error,
+        error => panic!("expected a pgn error, got {:?}", error),
+    }
+}
+
+#[test]
+fn reads_tags_and_results() {
+    let games = games();
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].tag("Event"), Some("Casual \"blitz\""));
+    assert_eq!(games[0].tag("White"), Some("Anderssen"));
+    assert_eq!(games[0].tags.len(), 5);
+    assert_eq!(games[0].result, GameResult::WhiteWins);
+    assert_eq!(games[1].result, GameResult::BlackWins);
+    assert_eq!(games[2].result, GameResult::Ongoing);
+}
+
+#[test]
+fn reads_movetext() {
+    let game = &games()[0];
+    assert_eq!(game.moves.len(), 45);
+    assert_eq!(game.moves[0].leading_comments, vec!["Opening comment"]);
+    let b5 = &game.moves[7];
+    assert_eq!(b5.san, "b5");
+    assert_eq!(b5.nags, vec![6, 10]);
+    let bxg1 = &game.moves[35];
+    assert_eq!(bxg1.san, "Bxg1");
+    assert_eq!(bxg1.variations.len(), 1);
+    let variation = &bxg1.variations[0];
+    assert_eq!(variation.len(), 4);
+    assert_eq!(variation[2].comments, vec!["a long comment that runs over two lines"]);
+    assert_eq!(variation[2].variations[0][1].san, "Nxg7+");
+    assert_eq!((variation[0].line, variation[0].column), (11, 8));
+    assert_eq!(games()[1].moves[2].comments, vec!["the worst move on the board"]);
+    game.validate().unwrap();
+}
+
+#[test]
+fn replays_onto_a_board() {
+    let games = games();
+    let board = games[0].replay().unwrap();
+    assert_eq!(board.history().len(), 45);
+    assert_eq!(board.game_status(), GameStatus::Checkmate { winner: Color::White });
+    let fools = games[1].replay().unwrap();
+    assert!(fools.game_status().is_over());
+    let from_fen = games[2].replay().unwrap();
+    assert_eq!(from_fen.to_fen(), "8/3k4/8/4P3/8/8/8/4K3 b - - 0 2");
+}
+
+#[test]
+fn points_at_bad_input() {
+    let error = first_error("1. e4 e5\n2. Nf3 {never closed\n3. Bb5");
+    assert!(matches!(error.kind, PgnErrorKind::UnterminatedComment));
+    assert_eq!((error.line, error.column), (2, 8));
+
+    let error = first_error("[Event \"x]\n1. e4 *");
+    assert!(matches!(error.kind, PgnErrorKind::UnterminatedTag));
+    assert_eq!(error.line, 1);
+
+    let error = first_error("1. e4 e5 (1... c5 2. Nf3 1-0) *");
+    assert!(matches!(error.kind, PgnErrorKind::ResultInVariation));
+    assert_eq!((error.line, error.column), (1, 26));
+
+    let error = first_error("1. e4 e5 ) *");
+    assert!(matches!(error.kind, PgnErrorKind::UnbalancedVariation));
+    assert_eq!((error.line, error.column), (1, 10));
+
+    let error = first_error("1. e4 (1. d4 d5");
+    assert!(matches!(error.kind, PgnErrorKind::UnterminatedVariation));
+
+    let error = first_error("1. e4 () e5 *");
+    assert!(matches!(error.kind, PgnErrorKind::EmptyVariation));
+    assert_eq!((error.line, error.column), (1, 8));
+
+    let error = first_error("1. e4 e5\n2. Nf3 ({only a comment}) Nc6 *");
+    assert!(matches!(error.kind, PgnErrorKind::EmptyVariation));
+    assert_eq!((error.line, error.column), (2, 25));
+
+    let error = first_error("( 1. e4 *");
+    assert!(matches!(error.kind, PgnErrorKind::VariationWithoutMove));
+
+    let error = first_error("1. e4 & *");
+    assert!(matches!(error.kind, PgnErrorKind::UnexpectedChar('&')));
+    assert_eq!((error.line, error.column), (1, 7));
+
+    let game: PgnGame = PgnReader::new("1. e4 e5\n2. Ke3 *".as_bytes()).next().unwrap().unwrap();
+    let Err(Error::Pgn(error)) = game.replay() else {
+        panic!("Ke3 is not legal");
+    };
+    assert!(matches!(error.kind, PgnErrorKind::IllegalMove(ref san) if san == "Ke3"));
+    assert_eq!((error.line, error.column), (2, 4));
+}
+
+#[test]
+fn errors_say_where() {
+    let error = first_error("1. e4 e5 $999 *");
+    assert_eq!(error.to_string(), "line 1, column 10: invalid NAG `$999`");
+    let error = first_error("[Event \"x\"]\n\n1. e4 {open *");
+    assert_eq!(
+        Error::Pgn(error).to_string(),
+        "line 3, column 7: comment without a closing `}`"
+    );
+}
+
+#[test]
+fn games_without_a_result_end_at_the_next_tags() {
+    let games: Vec<PgnGame> = PgnReader::new("[Event \"a\"]\n1. e4\n[Event \"b\"]\n1. d4 *".as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].moves.len(), 1);
+    assert_eq!(games[1].tag("Event"), Some("b"));
+    assert_eq!(games[1].moves[0].san, "d4");
+}
+
+#[test]
+fn comments_after_the_result_stay_with_the_game() {
+    let text = "[Event \"a\"]\n1. e4 e5 1-0 {White won}\n; on time\n\n[Event \"b\"]\n1. d4 * {unfinished}\n{between games}\n";
+    let games: Vec<PgnGame> = PgnReader::new(text.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].result, GameResult::WhiteWins);
+    assert_eq!(games[0].moves[1].comments, ["White won", "on time"]);
+    assert_eq!(games[1].tag("Event"), Some("b"));
+    assert_eq!(games[1].moves[0].comments, ["unfinished", "between games"]);
+    // a file with nothing but a note has no games in it
+    assert_eq!(PgnReader::new("{a note}\n; and another\n".as_bytes()).count(), 0);
+}
+
+#[test]
+fn writes_the_seven_tag_roster_first() {
+    let mut board = Board::new();
+    for san in ["f3", "e5", "g4", "Qh4#"] {
+        let action = board.parse_san(san).unwrap();
+        board.move_piece(action);
+    }
+    let mut game = PgnGame::from_board(&board);
+    game.set_tag("Annotator", "me");
+    game.set_tag("White", "Bot \"one\"");
+    assert_eq!(
+        game.to_string(),
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+         [White \"Bot \\\"one\\\"\"]\n[Black \"?\"]\n[Result \"0-1\"]\n[Annotator \"me\"]\n\n\
+         1. f3 e5 2. g4 Qh4# 0-1\n"
+    );
+}
+
+#[test]
+fn only_finished_games_get_a_result() {
+    // a repetition that could be claimed is not a draw yet
+    let mut board = Board::new();
+    for uci in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1", "f6g8"] {
+        let action = board.parse_uci_move(uci).unwrap();
+        board.move_piece(action);
+    }
+    assert_eq!(board.game_status(), GameStatus::ThreefoldRepetition);
+    assert_eq!(PgnGame::from_board(&board).result, GameResult::Ongoing);
+    let mut start = board.clone();
+    while start.undo().is_some() {}
+    let claimed = PgnGame::from_history(&start, board.history(), Some(GameResult::Draw));
+    assert_eq!(claimed.result, GameResult::Draw);
+
+    let stalemate = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(PgnGame::from_board(&stalemate).result, GameResult::Draw);
+    let bare_kings = Board::from_fen("7k/8/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(PgnGame::from_board(&bare_kings).result, GameResult::Draw);
+}
+
+#[test]
+fn writes_positions_comments_and_variations() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 7").unwrap();
+    for san in ["Kd7", "e4", "Kc6"] {
+        let action = board.parse_san(san).unwrap();
+        board.move_piece(action);
+    }
+    let game = PgnGame::from_board(&board);
+    assert_eq!(game.tag("FEN"), Some("4k3/8/8/8/8/8/4P3/4K3 b - - 0 7"));
+    let pgn = game.to_string();
+    assert!(pgn.ends_with("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 7\"]\n\n7... Kd7 8. e4 Kc6 *\n"), "{}", pgn);
+
+    let read = &games()[0];
+    let written = read.to_string().replace('\n', " ");
+    assert!(written.contains("{Opening comment} 1. e4 e5"));
+    assert!(written.contains("4. Kf1 b5 $6 $10 5. Bxb5"));
+    assert!(written.contains("18. Bd6 Bxg1 (18... Qxa1+ 19. Ke2 Qb2 {a long comment that runs over two lines}"));
+    assert!(written.contains("(19... Qxg1 20. Nxg7+) 20. Kd2) 19. e5"));
+    assert!(read.to_string().lines().all(|line| line.len() <= 80));
+}
+
+#[test]
+fn written_games_read_back_the_same() {
+    for game in games() {
+        let written = game.to_string();
+        let read = PgnReader::new(written.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(read.to_string(), written);
+        assert_eq!(read.replay().unwrap().to_fen(), game.replay().unwrap().to_fen());
+    }
+}
@@ -0,0 +1,61 @@
+use rusttesting::chess_engine::{
+    board::{Board, BoardPosition},
+    pieces::{Color, InnerPiece, Piece},
+};
+
+fn promotions(board: &Board) -> Vec<String> {
+    let mut moves: Vec<String> = board
+        .legal_moves()
+        .iter()
+        .filter(|action| action.promotion().is_some())
+        .map(|action| action.to_string())
+        .collect();
+    moves.sort();
+    moves
+}
+fn play(board: &mut Board, text: &str) {
+    let action = board
+        .legal_moves()
+        .into_iter()
+        .find(|action| action.to_string() == text)
+        .unwrap();
+    board.move_piece(action);
+}
+fn piece_on(board: &Board, square: &str) -> Option<Piece> {
+    board[&square.parse::<BoardPosition>().unwrap()]
+}
+
+#[test]
+fn every_piece_can_be_chosen() {
+    let board = Board::from_fen("1r6/P6k/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        promotions(&board),
+        ["a7a8b", "a7a8n", "a7a8q", "a7a8r", "a7b8b", "a7b8n", "a7b8q", "a7b8r"]
+    );
+}
+
+#[test]
+fn under_promotion() {
+    let mut board = Board::from_fen("8/P6k/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    play(&mut board, "a7a8n");
+    let knight = piece_on(&board, "a8").unwrap();
+    assert_eq!(knight.type_of_piece(), InnerPiece::Knight);
+    assert_eq!(knight.color, Color::White);
+    assert_eq!(board.to_fen(), "N7/7k/8/8/8/8/8/4K3 b - - 0 1");
+    board.undo();
+    assert_eq!(piece_on(&board, "a7").unwrap().type_of_piece(), InnerPiece::Pawn);
+    assert!(piece_on(&board, "a8").is_none());
+}
+
+#[test]
+fn capture_promotion_for_black() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/6p1/4K2R b - - 0 1").unwrap();
+    let before = board.to_fen();
+    let hash = board.hash();
+    play(&mut board, "g2h1q");
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K2q w - - 0 2");
+    assert!(board.history().last().unwrap().is_capture());
+    board.undo();
+    assert_eq!(board.to_fen(), before);
+    assert_eq!(board.hash(), hash);
+}
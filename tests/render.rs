@@ -0,0 +1,145 @@
+use rusttesting::chess_engine::{
+    board::{Bitboard, Board, BoardPosition, File, Rank},
+    render::{Arrow, PieceStyle, SvgRenderer, TextRenderer, Theme},
+};
+
+fn fools_mate() -> Board {
+    let mut board = Board::new();
+    for san in ["f3", "e5", "g4", "Qh4"] {
+        let action = board.parse_san(san).unwrap();
+        board.move_piece(action);
+    }
+    board
+}
+
+#[test]
+fn ascii_board_with_coordinates() {
+    let expected = "\
+8  r  n  b  q  k  b  n  r
+7  p  p  p  p  p  p  p  p
+6  .  .  .  .  .  .  .  .
+5  .  .  .  .  .  .  .  .
+4  .  .  .  .  .  .  .  .
+3  .  .  .  .  .  .  .  .
+2  P  P  P  P  P  P  P  P
+1  R  N  B  Q  K  B  N  R
+   a  b  c  d  e  f  g  h
+";
+    assert_eq!(Board::new().to_string(), expected);
+}
+
+#[test]
+fn flipped_board_has_black_at_the_bottom() {
+    let renderer = TextRenderer {
+        flipped: true,
+        ..Default::default()
+    };
+    let text = renderer.render(&Board::new());
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "1  R  N  B  K  Q  B  N  R");
+    assert_eq!(lines[7], "8  r  n  b  k  q  b  n  r");
+    assert_eq!(lines[8], "   h  g  f  e  d  c  b  a");
+}
+
+#[test]
+fn last_move_and_check_are_marked() {
+    let board = fools_mate();
+    let renderer = TextRenderer {
+        coordinates: false,
+        ..Default::default()
+    };
+    let text = renderer.render(&board);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], " r  n  b [.] k  b  n  r");
+    assert_eq!(lines[4], " .  .  .  .  .  .  P [q]");
+    assert_eq!(lines[7], " R  N  B  Q [K] B  N  R");
+}
+
+#[test]
+fn unicode_and_ansi() {
+    let renderer = TextRenderer {
+        style: PieceStyle::Unicode,
+        coordinates: false,
+        ..Default::default()
+    };
+    let text = renderer.render(&Board::new());
+    assert!(text.starts_with(" ♜  ♞  ♝  ♛  ♚  ♝  ♞  ♜"));
+    assert!(text.lines().last().unwrap().starts_with(" ♖  ♘  ♗  ♕  ♔"));
+    assert!(!text.contains('\x1b'));
+
+    let renderer = TextRenderer {
+        style: PieceStyle::Unicode,
+        ansi: true,
+        ..Default::default()
+    };
+    let text = renderer.render(&Board::new());
+    assert!(text.contains('\x1b'));
+    assert!(!text.contains('♔'));
+    assert!(text.lines().all(|line| !line.contains('♜') || line.ends_with("\x1b[0m")));
+}
+
+#[test]
+fn svg_is_self_contained() {
+    let svg = SvgRenderer::default().render(&Board::new());
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="400""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect").count(), 64);
+    assert_eq!(svg.matches("&#xFE0E;</text>").count(), 32);
+    assert!(!svg.contains("href"));
+    assert!(!svg.contains("<image"));
+}
+
+#[test]
+fn svg_size_theme_and_orientation() {
+    let renderer = SvgRenderer {
+        size: 800,
+        theme: Theme::green(),
+        coordinates: false,
+        ..Default::default()
+    };
+    let svg = renderer.render(&Board::new());
+    assert!(svg.contains(r#"viewBox="0 0 800 800""#));
+    // a1 is dark and in the bottom left corner
+    assert!(svg.contains(r##"<rect x="0.0" y="700.0" width="100.0" height="100.0" fill="#769656"/>"##));
+    assert!(!svg.contains("font-weight"));
+
+    let flipped = SvgRenderer {
+        flipped: true,
+        ..renderer
+    };
+    let svg = flipped.render(&Board::new());
+    // now it is in the top right
+    assert!(svg.contains(r##"<rect x="700.0" y="0.0" width="100.0" height="100.0" fill="#769656"/>"##));
+}
+
+#[test]
+fn svg_overlays() {
+    let board = fools_mate();
+    let last = board.history().last().unwrap();
+    let mut highlights = Bitboard::EMPTY;
+    highlights.set(BoardPosition::new(File::A, Rank::One).square());
+    let renderer = SvgRenderer {
+        highlights,
+        arrows: vec![Arrow::from(last)],
+        ..Default::default()
+    };
+    let svg = renderer.render(&board);
+    let theme = Theme::default();
+    // the two squares of the last move and the extra one
+    assert_eq!(svg.matches(&format!(r#"fill="{}""#, theme.highlight)).count(), 3);
+    // the white king on e1 is in check
+    assert!(svg.contains(&format!(r#"<circle cx="225.0" cy="375.0" r="22.5" fill="{}"/>"#, theme.check)));
+    // from the middle of d8 to the middle of h4
+    assert!(svg.contains(r#"<line x1="175.0" y1="25.0""#));
+    assert!(svg.contains(r#"<polygon points="375.0,225.0 "#));
+
+    let plain = SvgRenderer {
+        last_move: false,
+        check: false,
+        ..Default::default()
+    };
+    let svg = plain.render(&board);
+    assert!(!svg.contains(&theme.highlight));
+    assert!(!svg.contains("<circle"));
+    assert!(!svg.contains("<polygon"));
+}
@@ -0,0 +1,103 @@
+use std::io::BufRead;
+
+use common::Buffer;
+use rusttesting::{
+    chess_engine::board::{Board, STARTING_FEN},
+    repl::{EngineSide, Repl},
+};
+
+mod common;
+
+fn repl(engine: EngineSide) -> (Repl, Buffer) {
+    let buffer = Buffer::default();
+    let mut repl = Repl::with_output(engine, buffer.output());
+    repl.handle("time 0.05");
+    (repl, buffer)
+}
+
+#[test]
+fn runs_until_quit() {
+    let (mut repl, buffer) = repl(EngineSide::Off);
+    repl.run("e4\nquit\ne5\n".as_bytes().lines().map_while(Result::ok));
+    let text = buffer.text();
+    assert!(text.starts_with("type help for the commands\n"));
+    assert!(text.contains("1. e4\n"));
+    assert_eq!(text.matches("> ").count(), 2);
+    assert!(!text.contains("e5"));
+}
+
+#[test]
+fn eval_is_from_whites_side() {
+    let (mut repl, buffer) = repl(EngineSide::Off);
+    // white is a queen up but black is to move
+    repl.handle("new 3qk3/8/8/8/8/8/8/Q2QK3 b - - 0 1");
+    buffer.clear();
+    repl.handle("eval");
+    let text = buffer.text();
+    let phase = text.lines().find(|line| line.starts_with("phase")).unwrap();
+    assert!(phase.split_whitespace().nth(1).unwrap().parse::<i32>().is_ok());
+    let total = text.lines().find(|line| line.starts_with("total")).unwrap();
+    assert!(total.ends_with("(from white's side)"));
+    let score: i32 = total.split_whitespace().nth(1).unwrap().parse().unwrap();
+    assert!(score > 500, "{}", total);
+}
+
+#[test]
+fn undo_takes_back_a_move() {
+    let (mut repl, buffer) = repl(EngineSide::Off);
+    repl.handle("undo");
+    assert!(buffer.text().ends_with("nothing to undo\n"));
+    for line in ["e4", "e5", "undo"] {
+        repl.handle(line);
+    }
+    buffer.clear();
+    repl.handle("fen");
+    let mut board = Board::new();
+    let action = board.parse_san("e4").unwrap();
+    board.move_piece(action);
+    assert_eq!(buffer.text(), format!("{}\n", board.to_fen()));
+}
+
+#[test]
+fn undo_goes_back_past_the_engine_reply() {
+    let (mut repl, buffer) = repl(EngineSide::Black);
+    repl.handle("d4");
+    assert_eq!(repl.board().history().len(), 2);
+    assert!(buffer.text().contains("1... "));
+    repl.handle("undo");
+    assert_eq!(repl.board().to_fen(), STARTING_FEN);
+}
+
+#[test]
+fn moves_in_coordinates() {
+    let (mut repl, buffer) = repl(EngineSide::Off);
+    for line in ["g1f3", "e7e5", "f3e5"] {
+        repl.handle(line);
+    }
+    let mut board = Board::new();
+    for san in ["Nf3", "e5", "Nxe5"] {
+        let action = board.parse_san(san).unwrap();
+        board.move_piece(action);
+    }
+    assert_eq!(repl.board().to_fen(), board.to_fen());
+    assert!(buffer.text().contains("1. Nf3\n"));
+    assert!(buffer.text().contains("2. Nxe5\n"));
+    // a promotion written the same way
+    repl.handle("new 8/4P2k/8/8/8/8/8/4K3 w - - 0 1");
+    repl.handle("e7e8q");
+    assert!(buffer.text().contains("1. e8=Q\n"), "{}", buffer.text());
+}
+
+#[test]
+fn unknown_input_points_at_help() {
+    let (mut repl, buffer) = repl(EngineSide::Off);
+    repl.handle("castle");
+    // san that just isn't legal here says why instead
+    repl.handle("Ke2");
+    let text = buffer.text();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", text);
+    assert_eq!(lines[0], "not a move or command: castle, try help");
+    assert!(!lines[1].starts_with("not a move or command"), "{}", text);
+    assert_eq!(repl.board().to_fen(), STARTING_FEN);
+}
@@ -0,0 +1,82 @@
+use rusttesting::chess_engine::{board::Board, errors::SanError, Error};
+
+fn san_of(fen: &str, uci: &str) -> String {
+    let board = Board::from_fen(fen).unwrap();
+    let action = board
+        .legal_moves()
+        .into_iter()
+        .find(|action| action.to_string() == uci)
+        .unwrap();
+    action.to_san(&board)
+}
+
+#[test]
+fn formats_moves() {
+    let start = Board::new();
+    let board = start.to_fen();
+    assert_eq!(san_of(&board, "e2e4"), "e4");
+    assert_eq!(san_of(&board, "g1f3"), "Nf3");
+    let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    assert_eq!(san_of(kiwipete, "e1g1"), "O-O");
+    assert_eq!(san_of(kiwipete, "e1c1"), "O-O-O");
+    assert_eq!(san_of(kiwipete, "d5e6"), "dxe6");
+    assert_eq!(san_of(kiwipete, "e5f7"), "Nxf7");
+    assert_eq!(san_of(kiwipete, "f3f6"), "Qxf6");
+    assert_eq!(san_of("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), "b8=Q+");
+    assert_eq!(san_of("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"), "Ra8#");
+    assert_eq!(san_of("4k3/8/8/4pP2/8/8/8/4K3 w - e6 0 1", "f5e6"), "fxe6");
+}
+
+#[test]
+fn disambiguates() {
+    let knights = "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1";
+    assert_eq!(san_of(knights, "b1d2"), "Nbd2");
+    assert_eq!(san_of(knights, "f1d2"), "Nfd2");
+    let rooks = "4k3/8/8/R7/8/8/8/R3K3 w - - 0 1";
+    assert_eq!(san_of(rooks, "a1a3"), "R1a3");
+    assert_eq!(san_of(rooks, "a5a3"), "R5a3");
+    let queens = "8/2k5/8/8/4Q2Q/8/8/K6Q w - - 0 1";
+    assert_eq!(san_of(queens, "h4e1"), "Qh4e1");
+    assert_eq!(san_of(queens, "e4e1"), "Qee1");
+    assert_eq!(san_of(queens, "h1e1"), "Q1e1");
+}
+
+#[test]
+fn parses_what_it_formats() {
+    let positions = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "8/2k5/8/8/4Q2Q/8/8/K6Q w - - 0 1",
+    ];
+    for fen in positions {
+        let board = Board::from_fen(fen).unwrap();
+        for action in board.legal_moves().iter() {
+            let san = action.to_san(&board);
+            let parsed = board.parse_san(&san).unwrap();
+            assert_eq!(parsed.to_string(), action.to_string(), "{} in {}", san, fen);
+        }
+    }
+}
+
+#[test]
+fn parses_loose_input() {
+    let board = Board::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+    assert_eq!(board.parse_san("dxc8Q").unwrap().to_string(), "d7c8q");
+    assert_eq!(board.parse_san("dxc8=N!?").unwrap().to_string(), "d7c8n");
+    assert_eq!(board.parse_san("0-0").unwrap().to_string(), "e1g1");
+    assert_eq!(board.parse_san("Bf7").unwrap().to_string(), "c4f7");
+    assert_eq!(board.parse_san("Kxf2").unwrap().to_string(), "e1f2");
+}
+
+#[test]
+fn rejects_bad_input() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+    assert!(matches!(board.parse_san(""), Err(Error::San(SanError::Empty))));
+    assert!(matches!(board.parse_san("Nd2"), Err(Error::San(SanError::AmbiguousMove(_)))));
+    assert!(matches!(board.parse_san("Nd4"), Err(Error::San(SanError::IllegalMove(_)))));
+    assert!(matches!(board.parse_san("O-O"), Err(Error::San(SanError::IllegalMove(_)))));
+    assert!(matches!(board.parse_san("Nz9"), Err(Error::San(SanError::InvalidSan(_)))));
+    assert!(matches!(board.parse_san("hello"), Err(Error::San(SanError::InvalidSan(_)))));
+}
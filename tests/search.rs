@@ -0,0 +1,136 @@
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+use rusttesting::chess_engine::{
+    board::Board,
+    search::{Search, SearchLimits, SearchResult},
+};
+
+fn search(board: &mut Board, limits: SearchLimits) -> SearchResult {
+    Search::new(limits).run(board, |_| {})
+}
+fn to_depth(fen: &str, depth: u32) -> SearchResult {
+    let limits = SearchLimits {
+        depth: Some(depth),
+        ..Default::default()
+    };
+    search(&mut Board::from_fen(fen).unwrap(), limits)
+}
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board.parse_uci_move(text).unwrap();
+        board.move_piece(action);
+    }
+}
+
+#[test]
+fn mate_in_one() {
+    let result = to_depth("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 3);
+    assert_eq!(result.best_move.as_ref().unwrap().to_uci(), "a1a8");
+    assert_eq!(result.mate_in(), Some(1));
+}
+
+#[test]
+fn mate_in_two() {
+    // checking straight away lets the king out to a7, the white king has to cover it first
+    let fen = "k7/8/2K5/8/8/8/8/7R w - - 0 1";
+    let result = to_depth(fen, 4);
+    assert_eq!(result.mate_in(), Some(2));
+    assert_eq!(result.pv.len(), 3);
+    let mut board = Board::from_fen(fen).unwrap();
+    for action in &result.pv {
+        board.move_piece(action.clone());
+    }
+    assert!(board.legal_moves().is_empty() && board.is_in_check(board.turn()));
+
+    // and the side getting mated sees it coming
+    let mut board = Board::from_fen(fen).unwrap();
+    board.move_piece(result.best_move.unwrap());
+    let defence = search(
+        &mut board,
+        SearchLimits {
+            depth: Some(3),
+            ..Default::default()
+        },
+    );
+    assert_eq!(defence.mate_in(), Some(-1));
+}
+
+#[test]
+fn no_move_when_the_game_is_over() {
+    let mated = to_depth("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3", 3);
+    assert!(mated.best_move.is_none());
+    assert!(mated.pv.is_empty());
+    assert!(mated.mate_in().is_some_and(|moves| moves <= 0));
+    let stalemate = to_depth("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 3);
+    assert!(stalemate.best_move.is_none());
+    assert_eq!(stalemate.score, 0);
+}
+
+#[test]
+fn stops_at_the_depth_limit() {
+    let result = to_depth("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", 3);
+    assert_eq!(result.depth, 3);
+    assert_eq!(result.pv.len(), 3);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn stops_at_the_node_limit() {
+    let limits = SearchLimits {
+        nodes: Some(5_000),
+        ..Default::default()
+    };
+    let result = search(&mut Board::new(), limits);
+    assert!(result.nodes <= 5_000, "{}", result.nodes);
+    assert!(result.best_move.is_some());
+
+    // even a limit too small to finish the first depth leaves a move
+    let limits = SearchLimits {
+        nodes: Some(100),
+        ..Default::default()
+    };
+    let result = search(&mut Board::new(), limits);
+    assert!(result.nodes <= 100, "{}", result.nodes);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn stops_at_the_time_limit() {
+    let limits = SearchLimits {
+        time: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let result = search(&mut Board::new(), limits);
+    assert!(result.elapsed < Duration::from_secs(1), "{:?}", result.elapsed);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn a_set_stop_flag_still_leaves_a_move() {
+    let stop = Arc::new(AtomicBool::new(true));
+    let result = Search::with_stop(SearchLimits::default(), stop).run(&mut Board::new(), |_| {});
+    assert!(result.best_move.is_some());
+    // the flag is looked at every 1024 nodes, like the clock
+    assert!(result.nodes <= 1024, "{}", result.nodes);
+}
+
+#[test]
+fn repeating_is_scored_as_a_draw() {
+    let fen = "7k/8/8/7q/8/8/8/K7 w - - 0 1";
+    let lost = to_depth(fen, 3);
+    assert!(lost.score < -500, "{}", lost.score);
+
+    // back where it started, so going to b1 again repeats the position after it
+    let mut board = Board::from_fen(fen).unwrap();
+    play(&mut board, &["a1b1", "h5h4", "b1a1", "h4h5"]);
+    let limits = SearchLimits {
+        depth: Some(3),
+        ..Default::default()
+    };
+    let result = search(&mut board, limits);
+    assert_eq!(result.score, 0);
+    assert_eq!(result.best_move.as_ref().unwrap().to_uci(), "a1b1");
+}
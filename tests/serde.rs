@@ -0,0 +1,119 @@
+#![cfg(feature = "serde")]
+
+use rusttesting::chess_engine::{
+    board::{Board, BoardPosition, File, Rank},
+    pieces::{Color, InnerPiece, Piece},
+    serialization::ActionSeed,
+};
+use serde::de::DeserializeSeed;
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board.parse_uci_move(text).unwrap();
+        board.move_piece(action);
+    }
+}
+
+#[test]
+fn colors_squares_and_pieces() {
+    assert_eq!(serde_json::to_string(&Color::White).unwrap(), r#""white""#);
+    assert_eq!(
+        serde_json::from_str::<Color>(r#""black""#).unwrap(),
+        Color::Black
+    );
+    assert!(serde_json::from_str::<Color>(r#""red""#).is_err());
+
+    let square = BoardPosition::new(File::E, Rank::Four);
+    assert_eq!(serde_json::to_string(&square).unwrap(), r#""e4""#);
+    assert_eq!(
+        serde_json::from_str::<BoardPosition>(r#""e4""#).unwrap(),
+        square
+    );
+    assert!(serde_json::from_str::<BoardPosition>(r#""i9""#).is_err());
+
+    let knight = Piece::new(Color::White, InnerPiece::Knight);
+    assert_eq!(serde_json::to_string(&knight).unwrap(), r#""N""#);
+    assert_eq!(serde_json::from_str::<Piece>(r#""N""#).unwrap(), knight);
+    let pawn = serde_json::from_str::<Piece>(r#""p""#).unwrap();
+    assert_eq!(pawn, Piece::new(Color::Black, InnerPiece::Pawn));
+    assert!(serde_json::from_str::<Piece>(r#""x""#).is_err());
+}
+
+#[test]
+fn actions_are_coordinate_notation() {
+    let mut board = Board::new();
+    play(&mut board, &["e2e4", "d7d5", "e4e5", "f7f5"]);
+    let en_passant = board.parse_uci_move("e5f6").unwrap();
+    let json = serde_json::to_string(&en_passant).unwrap();
+    assert_eq!(json, r#""e5f6""#);
+
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let action = ActionSeed(&board).deserialize(&mut deserializer).unwrap();
+    assert!(action.is_en_passant());
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#""e5e7""#);
+    assert!(ActionSeed(&board).deserialize(&mut deserializer).is_err());
+}
+
+#[test]
+fn new_board_is_just_fen() {
+    let json = serde_json::to_string(&Board::new()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"}"#
+    );
+    let board: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(board.hash(), Board::new().hash());
+    assert!(board.history().is_empty());
+}
+
+#[test]
+fn board_round_trips_with_history() {
+    let start = "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    let mut board = Board::from_fen(start).unwrap();
+    play(&mut board, &["e1c1", "e8g8", "b7a8q", "f8a8"]);
+    let json = serde_json::to_value(&board).unwrap();
+    assert_eq!(json["start"], start);
+    assert_eq!(json["fen"], board.to_fen());
+    assert_eq!(
+        json["moves"],
+        serde_json::json!(["e1c1", "e8g8", "b7a8q", "f8a8"])
+    );
+    assert_eq!(
+        serde_json::to_value(board.history()).unwrap(),
+        json["moves"]
+    );
+
+    let mut restored: Board = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.to_fen(), board.to_fen());
+    assert_eq!(restored.hash(), board.hash());
+    assert_eq!(restored.history().len(), 4);
+    // the history is real, it can be taken back
+    restored.undo();
+    restored.undo();
+    assert_eq!(restored.to_fen(), "r4rk1/1P6/8/8/8/8/8/2KR3R w - - 2 2");
+}
+
+#[test]
+fn board_rejects_moves_that_dont_match() {
+    let bad_fen = r#"{"fen":"not a fen"}"#;
+    assert!(serde_json::from_str::<Board>(bad_fen).is_err());
+    let illegal = r#"{"fen":"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1","start":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e5"]}"#;
+    assert!(serde_json::from_str::<Board>(illegal).is_err());
+    // e2e4 leaves an en passant square the fen left out
+    let wrong_end = r#"{"fen":"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1","start":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e4"]}"#;
+    assert!(serde_json::from_str::<Board>(wrong_end).is_err());
+}
+
+#[test]
+fn errors_read_as_text() {
+    let message = |json: &str| serde_json::from_str::<Board>(json).unwrap_err().to_string();
+    let bad_fen = message(r#"{"fen":"8/8/8/8/8/8/8 w - - 0 1"}"#);
+    assert!(bad_fen.starts_with("invalid fen, 7 ranks instead of 8 (8/8/8/8/8/8/8 w - - 0 1)"), "{}", bad_fen);
+    let illegal = message(r#"{"fen":"8/8/8/8/8/8/8/8 w - - 0 1","start":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e5"]}"#);
+    assert!(illegal.starts_with("invalid move e2e5: "), "{}", illegal);
+    assert!(!illegal.contains("UciMove("), "{}", illegal);
+    let square = serde_json::from_str::<BoardPosition>(r#""i9""#).unwrap_err().to_string();
+    assert!(square.starts_with("invalid square i9: "), "{}", square);
+    assert!(!square.contains("BoardPosition("), "{}", square);
+}
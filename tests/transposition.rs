@@ -0,0 +1,51 @@
+use rusttesting::chess_engine::{
+    board::Board,
+    search::{Bound, Search, SearchLimits, TranspositionTable},
+};
+
+#[test]
+fn stores_and_probes_by_key() {
+    let mut table = TranspositionTable::new(1);
+    let key = Board::new().hash();
+    assert!(table.probe(key).is_none());
+    table.store(key, 4, 25, Bound::Exact, None);
+    let entry = table.probe(key).unwrap();
+    assert_eq!((entry.depth, entry.score, entry.bound), (4, 25, Bound::Exact));
+    // a key landing in the same slot is not mistaken for the stored one
+    assert!(table.probe(key.wrapping_add(table.len() as u64)).is_none());
+}
+
+#[test]
+fn keeps_deeper_entries_within_a_search() {
+    let mut table = TranspositionTable::new(1);
+    let key = 12345;
+    let other = key + table.len() as u64;
+    table.store(key, 6, 10, Bound::Exact, None);
+    table.store(other, 2, 20, Bound::Lower, None);
+    assert!(table.probe(key).is_some());
+    assert!(table.probe(other).is_none());
+    // entries from an older search make way
+    table.new_search();
+    table.store(other, 2, 20, Bound::Lower, None);
+    assert!(table.probe(key).is_none());
+    assert!(table.probe(other).is_some());
+    table.clear();
+    assert!(table.probe(other).is_none());
+}
+
+#[test]
+fn reused_table_gives_the_same_answer() {
+    let fen = "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1";
+    let limits = SearchLimits {
+        depth: Some(4),
+        ..Default::default()
+    };
+    let mut search = Search::new(limits.clone());
+    let first = search.run(&mut Board::from_fen(fen).unwrap(), |_| {});
+    let second = search.run(&mut Board::from_fen(fen).unwrap(), |_| {});
+    assert_eq!(first.best_move.as_ref().unwrap().to_string(), "a1a8");
+    assert_eq!(second.best_move.as_ref().unwrap().to_string(), "a1a8");
+    assert_eq!(first.mate_in(), Some(1));
+    assert_eq!(second.mate_in(), Some(1));
+    assert!(search.table().hashfull() <= 1000);
+}
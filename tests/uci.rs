@@ -0,0 +1,78 @@
+use std::{io::BufRead, thread, time::Duration};
+
+use common::Buffer;
+use rusttesting::{chess_engine::board::Board, uci::Uci};
+
+mod common;
+
+fn engine() -> (Uci, Buffer) {
+    let buffer = Buffer::default();
+    (Uci::with_output(buffer.output()), buffer)
+}
+// the first line starting with `prefix`
+fn wait_for(buffer: &Buffer, prefix: &str) -> String {
+    buffer.wait_until(|text| {
+        text.lines()
+            .find(|line| line.starts_with(prefix))
+            .map(String::from)
+    })
+}
+
+#[test]
+fn handshake() {
+    let (mut uci, buffer) = engine();
+    uci.run("uci\nisready\nquit\nisready\n".as_bytes().lines().map_while(Result::ok));
+    let text = buffer.text();
+    assert!(text.starts_with("id name "));
+    assert!(text.contains("option name Hash type spin"));
+    // nothing is read after quit
+    assert!(text.ends_with("uciok\nreadyok\n"), "{}", text);
+}
+
+#[test]
+fn go_depth_answers_with_a_bestmove_from_the_position() {
+    let (mut uci, buffer) = engine();
+    assert!(uci.handle("position startpos moves e2e4 e7e5 g1f3"));
+    uci.handle("go depth 2");
+    let bestmove = wait_for(&buffer, "bestmove ");
+    assert!(buffer.text().contains("info depth 2 "));
+    let mut board = Board::new();
+    for text in ["e2e4", "e7e5", "g1f3"] {
+        let action = board.parse_uci_move(text).unwrap();
+        board.move_piece(action);
+    }
+    let text = bestmove.trim_start_matches("bestmove ");
+    assert!(board.parse_uci_move(text).is_ok(), "{} is not a move for black", text);
+}
+
+#[test]
+fn mate_is_found_from_a_fen() {
+    let (mut uci, buffer) = engine();
+    uci.handle("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    uci.handle("go depth 3");
+    assert_eq!(wait_for(&buffer, "bestmove"), "bestmove a1a8");
+    assert!(buffer.text().contains("score mate 1"));
+}
+
+#[test]
+fn bad_positions_are_reported_and_ignored() {
+    let (mut uci, buffer) = engine();
+    uci.handle("position startpos moves e2e4 e2e4");
+    uci.handle("position fen 8/8/8/8/8/8/8/8 w - - 0 1");
+    let text = buffer.text();
+    assert_eq!(text.lines().count(), 2, "{}", text);
+    assert!(text.lines().all(|line| line.starts_with("info string invalid position: ")));
+}
+
+#[test]
+fn go_infinite_waits_for_stop() {
+    let (mut uci, buffer) = engine();
+    uci.handle("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    // the mate is found almost at once but the move has to wait
+    uci.handle("go infinite");
+    wait_for(&buffer, "info depth 1 ");
+    thread::sleep(Duration::from_millis(100));
+    assert!(!buffer.text().contains("bestmove"));
+    uci.handle("stop");
+    assert!(buffer.text().ends_with("bestmove a1a8\n"));
+}
@@ -0,0 +1,51 @@
+use rusttesting::chess_engine::{board::Board, errors::UciMoveError, Error};
+
+#[test]
+fn round_trips_every_legal_move() {
+    let positions = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "4k3/8/8/4pP2/8/8/8/4K3 w - e6 0 1",
+    ];
+    for fen in positions {
+        let board = Board::from_fen(fen).unwrap();
+        for action in board.legal_moves().iter() {
+            let parsed = board.parse_uci_move(&action.to_uci()).unwrap();
+            assert_eq!(parsed.to_uci(), action.to_uci());
+        }
+    }
+}
+
+#[test]
+fn parses_special_moves() {
+    let board = Board::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+    let castle = board.parse_uci_move("e1g1").unwrap();
+    assert!(castle.castle_side().is_some());
+    let promotion = board.parse_uci_move("d7c8n").unwrap();
+    assert!(promotion.is_capture());
+    assert_eq!(promotion.to_uci(), "d7c8n");
+    let en_passant = Board::from_fen("4k3/8/8/4pP2/8/8/8/4K3 w - e6 0 1")
+        .unwrap()
+        .parse_uci_move("f5e6")
+        .unwrap();
+    assert!(en_passant.is_en_passant());
+}
+
+#[test]
+fn rejects_bad_moves() {
+    let board = Board::new();
+    for text in ["", "e2", "e2e9", "e2e4qq", "i2i4", "e7e8k", "é2e4"] {
+        assert!(
+            matches!(board.parse_uci_move(text), Err(Error::UciMove(UciMoveError::InvalidMove(_)))),
+            "{}",
+            text
+        );
+    }
+    for text in ["e2e5", "e7e5", "e1g1", "e2e4q"] {
+        assert!(
+            matches!(board.parse_uci_move(text), Err(Error::UciMove(UciMoveError::IllegalMove(_)))),
+            "{}",
+            text
+        );
+    }
+}
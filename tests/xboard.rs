@@ -0,0 +1,112 @@
+use common::Buffer;
+use rusttesting::{chess_engine::board::Board, xboard::Xboard};
+
+mod common;
+
+fn engine() -> (Xboard, Buffer) {
+    let buffer = Buffer::default();
+    let mut xboard = Xboard::with_output(buffer.output());
+    for line in ["new", "sd 2", "st 5"] {
+        xboard.handle(line);
+    }
+    (xboard, buffer)
+}
+// the moves the engine sent so far, once there are at least `count` of them
+fn moves(buffer: &Buffer, count: usize) -> Vec<String> {
+    buffer.wait_until(|text| {
+        let moves: Vec<String> = text
+            .lines()
+            .filter_map(|line| line.strip_prefix("move "))
+            .map(String::from)
+            .collect();
+        (moves.len() >= count).then_some(moves)
+    })
+}
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board.parse_uci_move(text).unwrap();
+        board.move_piece(action);
+    }
+}
+
+#[test]
+fn features_and_ping() {
+    let (mut xboard, buffer) = engine();
+    xboard.handle("protover 2");
+    xboard.handle("ping 7");
+    let text = buffer.text();
+    assert!(text.starts_with("feature ping=1 setboard=1 usermove=1"));
+    assert!(text.contains("done=1\n"));
+    assert!(text.ends_with("pong 7\n"));
+}
+
+#[test]
+fn the_engine_move_is_played_before_the_next_command() {
+    let (mut xboard, buffer) = engine();
+    xboard.handle("usermove e2e4");
+    let reply = moves(&buffer, 1)[0].clone();
+    // ping doesn't touch the board but the move has to be on it by the time it is answered
+    xboard.handle("ping 1");
+    let mut board = Board::new();
+    play(&mut board, &["e2e4", &reply]);
+    assert_eq!(xboard.board().to_fen(), board.to_fen());
+
+    // and the gui's answer to it is legal
+    let answer = board.legal_moves()[0].to_uci();
+    xboard.handle(&format!("usermove {}", answer));
+    let replies = moves(&buffer, 2);
+    play(&mut board, &[&answer, &replies[1]]);
+    xboard.handle("force");
+    assert_eq!(xboard.board().to_fen(), board.to_fen());
+    assert!(!buffer.text().contains("Illegal"), "{}", buffer.text());
+}
+
+#[test]
+fn force_mode_only_follows_along() {
+    let (mut xboard, buffer) = engine();
+    for line in [
+        "force",
+        "usermove e2e4",
+        "usermove e7e5",
+        "undo",
+        "usermove c7c5",
+    ] {
+        xboard.handle(line);
+    }
+    let mut board = Board::new();
+    play(&mut board, &["e2e4", "c7c5"]);
+    assert_eq!(xboard.board().to_fen(), board.to_fen());
+    assert_eq!(buffer.text(), "");
+}
+
+#[test]
+fn go_mates_and_reports_the_result() {
+    let (mut xboard, buffer) = engine();
+    xboard.handle("setboard 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    xboard.handle("go");
+    assert_eq!(moves(&buffer, 1), ["a1a8"]);
+    xboard.handle("ping 2");
+    assert!(
+        buffer
+            .text()
+            .ends_with("move a1a8\n1-0 {White mates}\npong 2\n"),
+        "{}",
+        buffer.text()
+    );
+    assert!(xboard.board().game_status().is_over());
+}
+
+#[test]
+fn mistakes_are_reported() {
+    let (mut xboard, buffer) = engine();
+    xboard.handle("force");
+    xboard.handle("usermove e2e5");
+    xboard.handle("setboard 8/8/8/8/8/8/8/8 w - - 0 1");
+    xboard.handle("level 40 x 0");
+    let lines: Vec<String> = buffer.text().lines().map(String::from).collect();
+    assert_eq!(lines.len(), 3, "{:?}", lines);
+    assert!(lines[0].starts_with("Illegal move (") && lines[0].ends_with("): e2e5"));
+    assert!(lines[1].starts_with("tellusererror Illegal position: "));
+    assert_eq!(lines[2], "Error (bad level): level 40 x 0");
+    assert_eq!(xboard.board().to_fen(), Board::new().to_fen());
+}
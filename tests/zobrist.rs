@@ -0,0 +1,70 @@
+use rusttesting::chess_engine::board::{Board, GameStatus, STARTING_FEN};
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for text in moves {
+        let action = board
+            .legal_moves()
+            .into_iter()
+            .find(|action| action.to_string() == *text)
+            .unwrap();
+        board.move_piece(action);
+    }
+}
+
+#[test]
+fn transpositions_share_a_hash() {
+    let mut board = Board::new();
+    play(&mut board, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(board.hash(), Board::new().hash());
+
+    // the en passant squares differ but nothing can take on either
+    let mut a = Board::new();
+    play(&mut a, &["e2e4", "e7e5", "d2d4"]);
+    let mut b = Board::new();
+    play(&mut b, &["d2d4", "e7e5", "e2e4"]);
+    assert_ne!(a.to_fen(), b.to_fen());
+    assert_eq!(a.hash(), b.hash());
+}
+
+#[test]
+fn en_passant_only_counts_when_it_can_be_taken() {
+    let mut a = Board::new();
+    play(&mut a, &["e2e4", "d7d5", "e4e5", "f7f5"]);
+    let mut b = Board::new();
+    play(&mut b, &["e2e4", "f7f6", "e4e5", "f6f5", "g1f3", "d7d5", "f3g1"]);
+    // same pieces, but only a can take on f6
+    assert_eq!(a.to_fen().split(' ').next(), b.to_fen().split(' ').next());
+    assert_ne!(a.hash(), b.hash());
+    assert_eq!(a.hash(), Board::from_fen(&a.to_fen()).unwrap().hash());
+}
+
+#[test]
+fn the_position_after_a_double_step_can_repeat() {
+    let mut board = Board::new();
+    play(
+        &mut board,
+        &["e2e4", "g8f6", "g1f3", "f6g8", "f3g1", "g8f6", "g1f3", "f6g8", "f3g1"],
+    );
+    assert_eq!(board.repetitions(), 3);
+    assert_eq!(board.game_status(), GameStatus::ThreefoldRepetition);
+}
+
+#[test]
+fn incremental_hash_matches_fresh_hash() {
+    let mut board = Board::from_fen(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    )
+    .unwrap();
+    play(&mut board, &["a2a4", "b4a3", "e1c1", "h3g2", "e2d3", "g2h1q", "d1h1", "e8g8"]);
+    assert_eq!(board.hash(), Board::from_fen(&board.to_fen()).unwrap().hash());
+    while board.undo().is_some() {
+        assert_eq!(board.hash(), Board::from_fen(&board.to_fen()).unwrap().hash());
+    }
+}
+
+#[test]
+fn side_to_move_changes_the_hash() {
+    let black = STARTING_FEN.replace(" w ", " b ");
+    assert_ne!(Board::new().hash(), Board::from_fen(&black).unwrap().hash());
+    assert_eq!(Board::new().hash(), Board::from_fen(STARTING_FEN).unwrap().hash());
+}